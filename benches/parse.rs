@@ -1,5 +1,8 @@
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use qsv_dateparser::datetime::Parse;
 use qsv_dateparser::parse;
+use regex::{Regex, RegexBuilder};
 use std::sync::OnceLock;
 
 static SELECTED: OnceLock<Vec<&'static str>> = OnceLock::new();
@@ -7,26 +10,32 @@ static SELECTED: OnceLock<Vec<&'static str>> = OnceLock::new();
 fn bench_parse_all(c: &mut Criterion) {
     SELECTED
         .set(vec![
-            "2017-11-25T22:34:50Z",          // rfc3339
-            "Wed, 02 Jun 2021 06:31:39 GMT", // rfc2822
-            "2019-11-29 08:08:05-08",        // postgres_timestamp
-            "2021-04-30 21:14:10",           // ymd_hms
-            "2017-11-25 13:31:15 PST",       // ymd_hms_z
-            "2021-02-21",                    // ymd
-            "2021-02-21 PST",                // ymd_z
-            "May 27 02:45:27",               // month_md_hms
-            "May 8, 2009 5:57:51 PM",        // month_mdy_hms
-            "May 02, 2021 15:51 UTC",        // month_mdy_hms_z
-            "2021-Feb-21",                   // month_ymd
-            "May 25, 2021",                  // month_mdy
-            "14 May 2019 19:11:40.164",      // month_dmy_hms
-            "1 July 2013",                   // month_dmy
-            "03/19/2012 10:11:59",           // slash_mdy_hms
-            "08/21/71",                      // slash_mdy
-            "2012/03/19 10:11:59",           // slash_ymd_hms
-            "2014/3/31",                     // slash_ymd
-            "2014.03.30",                    // dot_mdy_or_ymd
-            "171113 14:14:20",               // mysql_log_timestamp
+            "2017-11-25T22:34:50Z",           // rfc3339
+            "Wed, 02 Jun 2021 06:31:39 GMT",  // rfc2822
+            "2019-11-29 08:08:05-08",         // postgres_timestamp
+            "2021-04-30 21:14:10",            // ymd_hms
+            "2017-11-25 13:31:15 PST",        // ymd_hms_z
+            "2021-02-21",                     // ymd
+            "2021-02-21 PST",                 // ymd_z
+            "May 27 02:45:27",                // month_md_hms
+            "May 8, 2009 5:57:51 PM",         // month_mdy_hms
+            "May 02, 2021 15:51 UTC",         // month_mdy_hms_z
+            "2021-Feb-21",                    // month_ymd
+            "May 25, 2021",                   // month_mdy
+            "14 May 2019 19:11:40.164",       // month_dmy_hms
+            "1 July 2013",                    // month_dmy
+            "03/19/2012 10:11:59",            // slash_mdy_hms
+            "08/21/71",                       // slash_mdy
+            "2012/03/19 10:11:59",            // slash_ymd_hms
+            "2014/3/31",                      // slash_ymd
+            "2014.03.30",                     // dot_mdy_or_ymd
+            "171113 14:14:20",                // mysql_log_timestamp
+            "Sunday, 06-Nov-94 08:49:37 GMT", // rfc850
+            "Wed Jun  2 06:31:39 2021",       // asctime
+            "Wed Jun  2 06:31:39 PDT 2021",   // unix_date
+            "[14/May/2021:18:51:00 -0700]",   // clf
+            "2021-05",                        // year_month
+            "2021-05-14 1430",                // time_of_day
         ])
         .unwrap();
     c.bench_with_input(
@@ -35,7 +44,7 @@ fn bench_parse_all(c: &mut Criterion) {
         |b, all| {
             b.iter(|| {
                 for date_str in all.iter() {
-                    let _ = parse(*date_str);
+                    let _ = parse(date_str);
                 }
             })
         },
@@ -50,5 +59,168 @@ fn bench_parse_each(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_parse_all, bench_parse_each);
+// a column of cells in the shapes a CSV export typically has, where the families near the end of
+// the cascade are the ones that benefit most from going straight to the right family
+fn bench_parse_column(c: &mut Criterion) {
+    let column: Vec<&str> = SELECTED
+        .get()
+        .unwrap()
+        .iter()
+        .cycle()
+        .take(10_000)
+        .copied()
+        .collect();
+    let mut group = c.benchmark_group("parse_column");
+    group.throughput(Throughput::Elements(column.len() as u64));
+    group.bench_with_input("mixed", &column, |b, column| {
+        b.iter(|| column.iter().filter(|input| parse(input).is_ok()).count())
+    });
+    let mut out = vec![None; column.len()];
+    group.bench_with_input("mixed_parse_many", &column, |b, column| {
+        let parser = Parse::new(&Local, Utc::now().time());
+        b.iter(|| parser.parse_many(column, &mut out))
+    });
+    group.finish();
+}
+
+// A cascade in the shape of the regex-per-family parser the lexer replaced: each family checks
+// its regex and then tries its chrono formats in turn. It only covers the families without a
+// timezone, which are the ones it is compared on; the real cascade also ran the families before
+// the matching one, so this is a lower bound on its cost.
+struct Cascade(Vec<(Regex, &'static [&'static str])>);
+
+impl Cascade {
+    fn new() -> Self {
+        let families: [(&str, &'static [&'static str]); 12] = [
+            (
+                r"^[0-9]{4}-[0-9]{2}-[0-9]{2}\s+[0-9]{2}:[0-9]{2}(:[0-9]{2})?(\.[0-9]{1,9})?\s*(am|pm|AM|PM)?$",
+                &[
+                    "%Y-%m-%d %H:%M:%S",
+                    "%Y-%m-%d %H:%M",
+                    "%Y-%m-%d %H:%M:%S%.f",
+                ],
+            ),
+            (r"^[0-9]{4}-[0-9]{2}-[0-9]{2}$", &["%Y-%m-%d"]),
+            (
+                r"^[0-9]{4}-[a-zA-Z]{3,9}-[0-9]{2}$",
+                &["%Y-%m-%d", "%Y-%b-%d"],
+            ),
+            (
+                r"^[a-zA-Z]{3,9}\.?\s+[0-9]{1,2},\s+[0-9]{2,4},?\s+[0-9]{1,2}:[0-9]{2}(:[0-9]{2})?\s*(am|pm|AM|PM)?$",
+                &[
+                    "%B %d, %Y, %H:%M:%S",
+                    "%B %d, %Y %H:%M:%S",
+                    "%B %d, %Y %I:%M:%S %p",
+                    "%B %d, %Y %I:%M %p",
+                ],
+            ),
+            (
+                r"^[a-zA-Z]{3,9}\.?\s+[0-9]{1,2},\s+[0-9]{2,4}$",
+                &["%B %d, %Y", "%B %d, %y"],
+            ),
+            (
+                r"^[0-9]{1,2}\s+[a-zA-Z]{3,9}\s+[0-9]{2,4},?\s+[0-9]{1,2}:[0-9]{2}(:[0-9]{2})?(\.[0-9]{1,9})?\s*(am|pm|AM|PM)?$",
+                &[
+                    "%d %B %Y %H:%M:%S",
+                    "%d %B %Y %H:%M",
+                    "%d %B %Y %H:%M:%S%.f",
+                ],
+            ),
+            (
+                r"^[0-9]{1,2}\s+[a-zA-Z]{3,9}\s+[0-9]{2,4}$",
+                &["%d %B %y", "%d %B %Y"],
+            ),
+            (
+                r"^[0-9]{1,2}/[0-9]{1,2}/[0-9]{2,4}\s+[0-9]{1,2}:[0-9]{2}(:[0-9]{2})?(\.[0-9]{1,9})?\s*(am|pm|AM|PM)?$",
+                &[
+                    "%m/%d/%y %H:%M:%S",
+                    "%m/%d/%y %H:%M",
+                    "%m/%d/%Y %H:%M:%S",
+                    "%m/%d/%Y %H:%M",
+                ],
+            ),
+            (
+                r"^[0-9]{1,2}/[0-9]{1,2}/[0-9]{2,4}$",
+                &["%m/%d/%y", "%m/%d/%Y"],
+            ),
+            (
+                r"^[0-9]{4}/[0-9]{1,2}/[0-9]{1,2}\s+[0-9]{1,2}:[0-9]{2}(:[0-9]{2})?(\.[0-9]{1,9})?\s*(am|pm|AM|PM)?$",
+                &[
+                    "%Y/%m/%d %H:%M:%S",
+                    "%Y/%m/%d %H:%M",
+                    "%Y/%m/%d %H:%M:%S%.f",
+                ],
+            ),
+            (r"^[0-9]{4}/[0-9]{1,2}/[0-9]{1,2}$", &["%Y/%m/%d"]),
+            (
+                r"^[a-zA-Z]{3}\s+[a-zA-Z]{3}\s+[0-9]{1,2}\s+[0-9]{2}:[0-9]{2}:[0-9]{2}\s+[0-9]{4}$",
+                &["%a %b %e %H:%M:%S %Y"],
+            ),
+        ];
+        Cascade(
+            families
+                .into_iter()
+                .map(|(re, formats)| {
+                    let re = RegexBuilder::new(re).unicode(false).build().unwrap();
+                    (re, formats)
+                })
+                .collect(),
+        )
+    }
+
+    fn parse(&self, input: &str) -> Option<NaiveDateTime> {
+        self.0
+            .iter()
+            .filter(|(re, _)| re.is_match(input))
+            .flat_map(|(_, formats)| formats.iter())
+            .find_map(|format| {
+                NaiveDateTime::parse_from_str(input, format)
+                    .or_else(|_| {
+                        NaiveDate::parse_from_str(input, format)
+                            .map(|date| date.and_time(NaiveTime::MIN))
+                    })
+                    .ok()
+            })
+    }
+}
+
+// the lexer against the regex cascade, on the inputs of SELECTED that the cascade reads
+fn bench_regex_baseline(c: &mut Criterion) {
+    let cascade = Cascade::new();
+    let inputs: Vec<&str> = SELECTED
+        .get()
+        .unwrap()
+        .iter()
+        .filter(|input| cascade.parse(input).is_some())
+        .copied()
+        .collect();
+    let parser = Parse::new(&Utc, Utc::now().time());
+    let mut group = c.benchmark_group("regex_baseline");
+    group.throughput(Throughput::Elements(inputs.len() as u64));
+    group.bench_with_input("tokens", &inputs, |b, inputs| {
+        b.iter(|| {
+            inputs
+                .iter()
+                .filter(|input| parser.parse(input).is_ok())
+                .count()
+        })
+    });
+    group.bench_with_input("regex_cascade", &inputs, |b, inputs| {
+        b.iter(|| {
+            inputs
+                .iter()
+                .filter(|input| cascade.parse(input).is_some())
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_parse_all,
+    bench_parse_each,
+    bench_parse_column,
+    bench_regex_baseline
+);
 criterion_main!(benches);
@@ -0,0 +1,129 @@
+use crate::datetime::{Batch, Parse};
+use anyhow::Result;
+use arrow_array::types::{
+    ArrowTimestampType, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType,
+};
+use arrow_array::{Array, ArrayRef, GenericStringArray, OffsetSizeTrait, PrimitiveArray};
+use arrow_buffer::NullBufferBuilder;
+use arrow_schema::TimeUnit;
+use chrono::prelude::*;
+use std::sync::Arc;
+
+/// Parses each value of a `StringArray` or `LargeStringArray` into a timestamp array of the given
+/// unit, with `timezone` as its metadata. Whatever the metadata, the values count from the unix
+/// epoch in UTC, as Arrow defines them; inputs without an offset are read in the timezone of
+/// `parse`.
+///
+/// Null values stay null. A value that doesn't parse, or that is out of range for the unit, is
+/// null too, unless `strict` is set, in which case the first one is returned as an error.
+pub fn to_timestamp<O, Tz2>(
+    parse: &Parse<'_, Tz2>,
+    array: &GenericStringArray<O>,
+    unit: TimeUnit,
+    timezone: Option<Arc<str>>,
+    strict: bool,
+) -> Result<ArrayRef>
+where
+    O: OffsetSizeTrait,
+    Tz2: TimeZone,
+{
+    let to_value = match unit {
+        TimeUnit::Second => |datetime: DateTime<Utc>| Some(datetime.timestamp()),
+        TimeUnit::Millisecond => |datetime: DateTime<Utc>| Some(datetime.timestamp_millis()),
+        TimeUnit::Microsecond => |datetime: DateTime<Utc>| Some(datetime.timestamp_micros()),
+        TimeUnit::Nanosecond => |datetime: DateTime<Utc>| datetime.timestamp_nanos_opt(),
+    };
+
+    let mut batch = Batch::new(parse);
+    let mut values = Vec::with_capacity(array.len());
+    let mut nulls = NullBufferBuilder::new(array.len());
+    for input in array.iter() {
+        let Some(input) = input else {
+            values.push(0);
+            nulls.append_null();
+            continue;
+        };
+        match batch.parse_value(input, strict, to_value)? {
+            Some(value) => {
+                values.push(value);
+                nulls.append_non_null();
+            }
+            None => {
+                values.push(0);
+                nulls.append_null();
+            }
+        }
+    }
+
+    let nulls = nulls.finish();
+    Ok(match unit {
+        TimeUnit::Second => timestamps::<TimestampSecondType>(values, nulls, timezone),
+        TimeUnit::Millisecond => timestamps::<TimestampMillisecondType>(values, nulls, timezone),
+        TimeUnit::Microsecond => timestamps::<TimestampMicrosecondType>(values, nulls, timezone),
+        TimeUnit::Nanosecond => timestamps::<TimestampNanosecondType>(values, nulls, timezone),
+    })
+}
+
+#[inline]
+fn timestamps<T: ArrowTimestampType>(
+    values: Vec<i64>,
+    nulls: Option<arrow_buffer::NullBuffer>,
+    timezone: Option<Arc<str>>,
+) -> ArrayRef {
+    Arc::new(PrimitiveArray::<T>::new(values.into(), nulls).with_timezone_opt(timezone))
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use super::*;
+    use arrow_array::{LargeStringArray, StringArray, TimestampNanosecondArray};
+    use arrow_schema::DataType;
+
+    #[test]
+    fn to_timestamp() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+        let array = StringArray::from(vec![
+            Some("2021-04-30 21:14:10"),
+            None,
+            Some("not-a-date"),
+            Some("9999-12-31 23:59:59"),
+            Some("1620021848"),
+        ]);
+
+        let parsed = super::to_timestamp(
+            &parse,
+            &array,
+            TimeUnit::Nanosecond,
+            Some("UTC".into()),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.data_type(),
+            &DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into()))
+        );
+        let parsed = parsed
+            .as_any()
+            .downcast_ref::<TimestampNanosecondArray>()
+            .unwrap();
+        assert_eq!(parsed.null_count(), 3);
+        assert_eq!(parsed.value(0), 1_619_817_250_000_000_000);
+        assert_eq!(parsed.value(4), 1_620_021_848_000_000_000);
+
+        let err = super::to_timestamp(&parse, &array, TimeUnit::Nanosecond, None, true)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "not-a-date did not match any formats.");
+
+        let array = LargeStringArray::from(vec!["9999-12-31 23:59:59"]);
+        let parsed = super::to_timestamp(&parse, &array, TimeUnit::Second, None, true).unwrap();
+        assert_eq!(
+            parsed.data_type(),
+            &DataType::Timestamp(TimeUnit::Second, None)
+        );
+        assert!(parsed.is_valid(0));
+        assert!(super::to_timestamp(&parse, &array, TimeUnit::Nanosecond, None, true).is_err());
+    }
+}
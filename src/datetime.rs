@@ -1,25 +1,357 @@
 #![allow(deprecated)]
+use crate::format;
+use crate::lexer::{self, Cursor, Layout, Lexed, Separator, Shape};
 use crate::timezone;
 use anyhow::{anyhow, Result};
+use chrono::format::{Fixed, Item, Parsed, StrftimeItems};
 use chrono::prelude::*;
-use regex::Regex;
-
-macro_rules! regex {
-    ($re:literal $(,)?) => {{
-        static RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
-        RE.get_or_init(|| unsafe {
-            regex::RegexBuilder::new($re)
-                .unicode(false)
-                .build()
-                .unwrap_unchecked()
-        })
-    }};
+use std::fmt;
+use std::sync::Arc;
+
+/// How a two-digit year, such as the `71` in `08/21/71`, is expanded to a full year.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TwoDigitYear {
+    /// Expand into the hundred years starting at the given pivot year. The default,
+    /// `Pivot(1969)`, maps `69..=99` to 1969-1999 and `00..=68` to 2000-2068.
+    Pivot(i32),
+    /// Expand into the hundred years that end the given number of years after the reference
+    /// clock's year. `Sliding(50)` is the RFC 9110 rule: a year that would be more than 50 years
+    /// in the future is taken to be in the past.
+    Sliding(i32),
+    /// Don't accept two-digit years. Inputs that have one do not match.
+    Reject,
+}
+
+impl Default for TwoDigitYear {
+    fn default() -> Self {
+        Self::Pivot(1969)
+    }
+}
+
+impl TwoDigitYear {
+    /// Expand the two-digit year `yy` (0-99) to a full year, or `None` when the policy rejects
+    /// two-digit years.
+    pub const fn expand(self, yy: i32, reference_year: i32) -> Option<i32> {
+        let start = match self {
+            Self::Pivot(pivot) => pivot,
+            Self::Sliding(ahead) => reference_year + ahead - 99,
+            Self::Reject => return None,
+        };
+        let year = start - start.rem_euclid(100) + yy;
+        Some(if year < start { year + 100 } else { year })
+    }
+}
+
+/// How much of a point in time an input specified, from the coarsest to the finest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Precision {
+    /// A year, e.g. `2021` or `FY2022`.
+    Year,
+    /// A half year, e.g. `H1 2021`.
+    HalfYear,
+    /// A quarter, e.g. `Q2 2021` or `FY2022 Q1`.
+    Quarter,
+    /// A month, e.g. `2021-05` or `May 2021`.
+    Month,
+    /// A calendar day, e.g. `2021-05-14` or `May 14`.
+    Day,
+    /// A date with a time of day, or an exact instant such as a unix timestamp.
+    Time,
+}
+
+impl Precision {
+    /// The first day after the period of this precision that starts on `start`. [`Precision::Time`]
+    /// has no period and returns `start`.
+    pub fn period_end(self, start: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Year => start.checked_add_months(chrono::Months::new(12)),
+            Self::HalfYear => start.checked_add_months(chrono::Months::new(6)),
+            Self::Quarter => start.checked_add_months(chrono::Months::new(3)),
+            Self::Month => start.checked_add_months(chrono::Months::new(1)),
+            Self::Day => start.succ_opt(),
+            Self::Time => Some(start),
+        }
+    }
+}
+
+/// How a partial date, such as `2021` or `May 2021`, is turned into a point in time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PartialFill {
+    /// The first instant of the period, e.g. `2021-01-01 00:00:00` for `2021`.
+    #[default]
+    Start,
+    /// The last instant of the period, e.g. `2021-12-31 23:59:59.999999999` for `2021`.
+    End,
+    /// Don't accept partial dates.
+    Reject,
+}
+
+/// How a leap second, such as `2016-12-31 23:59:60`, is represented.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LeapSecond {
+    /// Keep it, using `chrono`'s convention of a nanosecond value of one second or more.
+    #[default]
+    Preserve,
+    /// Clamp it to the last instant of the preceding second, e.g. `23:59:59.999999999`.
+    Clamp,
+    /// Don't accept leap seconds.
+    Reject,
+}
+
+// the number of inputs each thread of a parallel batch takes at a time; a multiple of 8 so each
+// chunk of the validity bitmap is whole bytes
+#[cfg(feature = "rayon")]
+const CHUNK: usize = 4096;
+
+// How the families write the time of day after their date, starting from the most lenient: an
+// hour of one or two digits, optional seconds with a fraction, and am or pm.
+const HMS: Layout = Layout {
+    separator: Separator::Space,
+    padded: false,
+    seconds: false,
+    fraction: true,
+    meridiem: true,
+};
+
+// hh:mm:ss and nothing else, as in logs and HTTP dates
+const LOG_HMS: Layout = Layout {
+    separator: Separator::Space,
+    padded: true,
+    seconds: true,
+    fraction: false,
+    meridiem: false,
+};
+
+/// The groups of formats that an input is matched against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Family {
+    HttpDate,
+    Partial,
+    UnixTimestamp,
+    SlashMdy,
+    SlashYmd,
+    Ymd,
+    MonthYmd,
+    MonthMdy,
+    MonthDmy,
+    UnixDate,
+    Clf,
+    Custom,
+}
+
+impl Family {
+    // Only the families that can match an input of this shape are tried, in the same order as
+    // for an input of unknown shape, so an input gets the same result either way.
+    #[inline]
+    const fn candidates(shape: Shape) -> &'static [Family] {
+        use Family::*;
+
+        match shape {
+            Shape::Numeric => &[Partial, UnixTimestamp],
+            Shape::YearDash => &[Partial, Ymd],
+            Shape::YearDashWord => &[Partial, MonthYmd],
+            Shape::YearSlash => &[SlashYmd],
+            Shape::DaySlash => &[Partial, SlashMdy],
+            Shape::DaySlashWord | Shape::Bracket => &[Clf],
+            Shape::DayWord => &[HttpDate, MonthDmy],
+            Shape::WordComma => &[HttpDate],
+            Shape::WordDigits => &[Partial, MonthMdy],
+            Shape::WordWord => &[HttpDate, Partial, UnixDate],
+            Shape::Other => &[
+                HttpDate,
+                Partial,
+                UnixTimestamp,
+                SlashMdy,
+                SlashYmd,
+                Ymd,
+                MonthYmd,
+                MonthMdy,
+                MonthDmy,
+                UnixDate,
+                Clf,
+            ],
+        }
+    }
+
+    // Whether the family reads some inputs that a family after it reads too, as the partial date
+    // 2021 is also a unix timestamp, and an RFC 2822 date is also a dd Mon yyyy one. The other
+    // families read inputs no other family does.
+    #[inline]
+    const fn overlaps(self) -> bool {
+        matches!(self, Family::HttpDate | Family::Partial)
+    }
+
+    #[inline]
+    const fn name(self) -> &'static str {
+        match self {
+            Family::HttpDate => "rfc2822, rfc850 or asctime",
+            Family::Partial => "partial date",
+            Family::UnixTimestamp => "unix timestamp",
+            Family::SlashMdy => "mm/dd/yyyy or dd/mm/yyyy",
+            Family::SlashYmd => "yyyy/mm/dd",
+            Family::Ymd => "yyyy-mm-dd",
+            Family::MonthYmd => "yyyy-mon-dd",
+            Family::MonthMdy => "Mon dd, yyyy",
+            Family::MonthDmy => "dd Mon yyyy",
+            Family::UnixDate => "unix date",
+            Family::Clf => "common log format",
+            Family::Custom => "custom format",
+        }
+    }
+}
+
+/// Parses the inputs of a batch in turn, reading the clock once and trying the family that parsed
+/// the previous input first.
+pub(crate) struct Batch<'z, Tz2> {
+    parse: Parse<'z, Tz2>,
+    hint: Option<Family>,
+}
+
+impl<'z, Tz2> Batch<'z, Tz2>
+where
+    Tz2: TimeZone,
+{
+    pub(crate) fn new(parse: &Parse<'z, Tz2>) -> Self {
+        Self {
+            parse: parse.pinned(),
+            hint: None,
+        }
+    }
+
+    /// Like [`Parse::parse`], but `None` when no format matched, which saves building an error
+    /// for every value of a column that has many.
+    #[inline]
+    pub(crate) fn parse(&mut self, input: &str) -> Option<Result<DateTime<Utc>>> {
+        self.parse
+            .parse_hinted(input.as_bytes(), &mut self.hint)
+            .map(|parsed| parsed.map(|datetime| datetime.with_timezone(&Utc)))
+    }
+
+    /// Parses the input and converts it with `to_value`, such as to a count of some time unit.
+    /// An input that doesn't parse or convert is `None`, or an error when `strict`.
+    #[cfg(any(feature = "arrow", feature = "polars"))]
+    #[inline]
+    pub(crate) fn parse_value<T>(
+        &mut self,
+        input: &str,
+        strict: bool,
+        to_value: impl Fn(DateTime<Utc>) -> Option<T>,
+    ) -> Result<Option<T>> {
+        let err = match self.parse(input) {
+            Some(Ok(datetime)) => match to_value(datetime) {
+                Some(value) => return Ok(Some(value)),
+                None => anyhow!("{} is out of range.", input),
+            },
+            _ if !strict => return Ok(None),
+            Some(Err(err)) => err,
+            None => anyhow!("{} did not match any formats.", input),
+        };
+        if strict {
+            Err(err)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// What an input says about when it is, as returned by [`Parse::parse_naive`]: the date and time
+/// as written when it has no offset, so the caller can localize it by its own rules, or else the
+/// instant at the offset it was written with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reading {
+    /// A date and time without an offset, such as `2021-04-30 21:14:10`.
+    Naive(NaiveDateTime),
+    /// A date without a time of day or an offset, such as `2021-04-30` or `May 2021`.
+    NaiveDate(NaiveDate),
+    /// A date and time with an offset or a timezone abbreviation, such as
+    /// `2021-05-14 18:51 PDT`. Unix timestamps are UTC.
+    Offset(DateTime<FixedOffset>),
+}
+
+/// How an input was written, as returned by [`Parse::parse_with_template`]: its fields in order,
+/// with their separators, zero-padding, the case of month and weekday names and 12 or 24-hour time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateTemplate {
+    fields: Vec<format::Field>,
+    offset: Option<i32>,
+}
+
+impl DateTemplate {
+    /// Writes the datetime in the style of the input, at its own offset. A timezone abbreviation
+    /// such as `PDT` is kept while the datetime is at the offset it stands for, and becomes the
+    /// name of the timezone of the datetime otherwise, such as `JST` with `chrono_tz` or `+09:00`
+    /// with `FixedOffset`. The `Z` of UTC becomes an offset such as `+09:00` likewise.
+    pub fn render<Tz>(&self, datetime: &DateTime<Tz>) -> String
+    where
+        Tz: TimeZone,
+        Tz::Offset: fmt::Display,
+    {
+        format::render(&self.fields, self.offset, datetime)
+    }
+
+    /// The strftime pattern of the template, as returned by [`Parse::detect_format`]. Patterns
+    /// don't keep the case of names, which `render` does.
+    pub fn pattern(&self) -> String {
+        format::strftime(&self.fields)
+    }
+}
+
+/// A timezone that is UTC, but records the last local time it was asked to localize. The formats
+/// only localize inputs that have no offset, so parsing with it tells those apart.
+#[derive(Debug, Clone, Default)]
+struct Wall {
+    local: std::cell::Cell<Option<NaiveDateTime>>,
+}
+
+impl TimeZone for Wall {
+    type Offset = FixedOffset;
+
+    fn from_offset(_: &FixedOffset) -> Self {
+        Self::default()
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> chrono::LocalResult<FixedOffset> {
+        self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_local_datetime(
+        &self,
+        local: &NaiveDateTime,
+    ) -> chrono::LocalResult<FixedOffset> {
+        self.local.set(Some(*local));
+        chrono::LocalResult::Single(FixedOffset::east_opt(0).unwrap())
+    }
+
+    fn offset_from_utc_date(&self, _: &NaiveDate) -> FixedOffset {
+        FixedOffset::east_opt(0).unwrap()
+    }
+
+    fn offset_from_utc_datetime(&self, _: &NaiveDateTime) -> FixedOffset {
+        FixedOffset::east_opt(0).unwrap()
+    }
+}
+
+/// A strftime pattern registered with [`Parse::custom_format_with`].
+#[derive(Debug, Clone)]
+struct CustomFormat {
+    items: Vec<Item<'static>>,
+    // the position of %Z in `items`, if any
+    zone: Option<usize>,
+    prefilter: Option<regex::bytes::Regex>,
+    priority: i32,
 }
+
 /// Parse struct has methods implemented parsers for accepted formats.
 pub struct Parse<'z, Tz2> {
     tz: &'z Tz2,
     default_time: NaiveTime,
     prefer_dmy: bool,
+    reference: Option<DateTime<Utc>>,
+    two_digit_year: TwoDigitYear,
+    partial_fill: PartialFill,
+    fiscal_year_start: u32,
+    leap_second: LeapSecond,
+    custom: Option<Arc<[CustomFormat]>>,
 }
 
 impl<'z, Tz2> Parse<'z, Tz2>
@@ -33,6 +365,12 @@ where
             tz,
             default_time,
             prefer_dmy: false,
+            reference: None,
+            two_digit_year: TwoDigitYear::Pivot(1969),
+            partial_fill: PartialFill::Start,
+            fiscal_year_start: 1,
+            leap_second: LeapSecond::Preserve,
+            custom: None,
         }
     }
 
@@ -41,6 +379,124 @@ where
         self
     }
 
+    /// Pin the reference clock used to fill in missing date parts (e.g. the year of a syslog
+    /// timestamp). When not set, the current time is used.
+    pub fn reference_time(&mut self, now: DateTime<Utc>) -> &mut Self {
+        self.reference = Some(now);
+        self
+    }
+
+    /// Set the policy used to expand two-digit years. Defaults to [`TwoDigitYear::Pivot`]`(1969)`.
+    pub fn two_digit_year(&mut self, policy: TwoDigitYear) -> &mut Self {
+        self.two_digit_year = policy;
+        self
+    }
+
+    /// Set how partial dates such as `2021`, `May 2021` or `May 14` are filled in. Defaults to
+    /// [`PartialFill::Start`].
+    pub fn partial_fill(&mut self, fill: PartialFill) -> &mut Self {
+        self.partial_fill = fill;
+        self
+    }
+
+    /// Set the month (1-12) that fiscal years start in, used for inputs such as `FY2022 Q1`. A
+    /// fiscal year is named after the calendar year it ends in, so with a start month of 10,
+    /// `FY2022` runs from 2021-10-01 to 2022-09-30. Defaults to 1, the calendar year.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `month` is not in `1..=12`.
+    pub fn fiscal_year_start(&mut self, month: u32) -> &mut Self {
+        assert!(
+            (1..=12).contains(&month),
+            "fiscal year start month {} is not in 1..=12",
+            month
+        );
+        self.fiscal_year_start = month;
+        self
+    }
+
+    /// Set how leap seconds are represented. Defaults to [`LeapSecond::Preserve`].
+    pub fn leap_second(&mut self, leap_second: LeapSecond) -> &mut Self {
+        self.leap_second = leap_second;
+        self
+    }
+
+    /// Register a strftime pattern, such as `%d-%b-%Y %Hh%M`, to parse inputs that none of the
+    /// accepted formats match. Like [`Parse::custom_format_with`] without a prefilter and with
+    /// priority 0.
+    pub fn custom_format(&mut self, pattern: &str) -> Result<&mut Self> {
+        self.custom_format_with(pattern, None, 0)
+    }
+
+    /// Register a strftime pattern to parse inputs with, tried only on inputs the `prefilter`
+    /// regex matches, if any. Prefilters are ASCII-only and match the bytes of the input, so `\d`
+    /// is `[0-9]`. A pattern with a positive `priority` is tried before the accepted
+    /// formats and the others after them, higher priorities first and then in the order they
+    /// were registered.
+    ///
+    /// The timezone is handled as in the accepted formats: an input with an offset (`%z`, `%:z`)
+    /// or a timezone abbreviation (`%Z`) is at that offset, and one without is in the timezone of
+    /// `self`, at the default time when the pattern has no time of day. A `%y` two-digit year is
+    /// expanded by the [`TwoDigitYear`] policy.
+    ///
+    /// ```
+    /// use chrono::prelude::*;
+    /// use qsv_dateparser::datetime::Parse;
+    ///
+    /// let mut parse = Parse::new(&Utc, NaiveTime::MIN);
+    /// parse
+    ///     .custom_format("%d-%b-%Y %Hh%M")?
+    ///     .custom_format_with("%Y.%m.%d-%H:%M:%S %Z", Some(r"^\d{4}\.\d{2}\."), 1)?;
+    /// assert_eq!(
+    ///     parse.parse("14-May-2021 18h51")?,
+    ///     Utc.with_ymd_and_hms(2021, 5, 14, 18, 51, 0).unwrap(),
+    /// );
+    /// assert_eq!(
+    ///     parse.parse("2021.05.14-18:51:00 PDT")?,
+    ///     Utc.with_ymd_and_hms(2021, 5, 15, 1, 51, 0).unwrap(),
+    /// );
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn custom_format_with(
+        &mut self,
+        pattern: &str,
+        prefilter: Option<&str>,
+        priority: i32,
+    ) -> Result<&mut Self> {
+        let items = StrftimeItems::new(pattern)
+            .parse_to_owned()
+            .map_err(|_| anyhow!("{} is not a valid strftime format.", pattern))?;
+        let zone = items
+            .iter()
+            .position(|item| matches!(item, Item::Fixed(Fixed::TimezoneName)));
+        let prefilter = prefilter
+            .map(|prefilter| {
+                regex::bytes::RegexBuilder::new(prefilter)
+                    .unicode(false)
+                    .build()
+                    .map_err(|err| anyhow!("{}: {}", prefilter, err))
+            })
+            .transpose()?;
+        let mut custom = self.custom.as_deref().unwrap_or_default().to_vec();
+        custom.push(CustomFormat {
+            items,
+            zone,
+            prefilter,
+            priority,
+        });
+        // stable, so formats of the same priority keep the order they were registered in
+        custom.sort_by_key(|format| std::cmp::Reverse(format.priority));
+        self.custom = Some(custom.into());
+        Ok(self)
+    }
+
+    #[cfg(any(feature = "jiff", feature = "time"))]
+    #[inline]
+    pub(crate) const fn default_time(&self) -> NaiveTime {
+        self.default_time
+    }
+
     /// Create a new instance of [`Parse`] with a custom parsing timezone that handles the
     /// datetime string without time offset, and the date parsing preference.
     pub const fn new_with_preference(
@@ -52,6 +508,12 @@ where
             tz,
             default_time,
             prefer_dmy,
+            reference: None,
+            two_digit_year: TwoDigitYear::Pivot(1969),
+            partial_fill: PartialFill::Start,
+            fiscal_year_start: 1,
+            leap_second: LeapSecond::Preserve,
+            custom: None,
         }
     }
 
@@ -59,1221 +521,3690 @@ where
     /// more examples from [`Parse`], [`crate::parse()`] and [`crate::parse_with_timezone()`].
     #[inline]
     pub fn parse(&self, input: &str) -> Result<DateTime<Utc>> {
-        self.rfc2822(input)
-            .or_else(|| self.unix_timestamp(input))
-            .or_else(|| self.slash_mdy_family(input))
-            .or_else(|| self.slash_ymd_family(input))
-            .or_else(|| self.ymd_family(input))
-            .or_else(|| self.month_ymd(input))
-            .or_else(|| self.month_mdy_family(input))
-            .or_else(|| self.month_dmy_family(input))
-            .unwrap_or_else(|| Err(anyhow!("{} did not match any formats.", input)))
+        self.parse_fixed_offset(input)
+            .map(|parsed| parsed.with_timezone(&Utc))
     }
 
-    #[inline]
-    fn ymd_family(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {
-            r"^[0-9]{4}-[0-9]{2}"
+    /// Like [`Parse::parse`], but an input without an offset is returned as written instead of
+    /// being localized in the timezone of `self`, which can't always be undone exactly: a time
+    /// repeated by a DST transition is read as one of the two, and a skipped one fails.
+    ///
+    /// ```
+    /// use chrono::prelude::*;
+    /// use qsv_dateparser::datetime::{Parse, Reading};
+    ///
+    /// let parse = Parse::new(&Utc, NaiveTime::MIN);
+    /// assert_eq!(
+    ///     parse.parse_naive("2021-04-30 21:14:10").unwrap(),
+    ///     Reading::Naive(NaiveDate::from_ymd_opt(2021, 4, 30).unwrap().and_hms_opt(21, 14, 10).unwrap()),
+    /// );
+    /// assert_eq!(
+    ///     parse.parse_naive("May 2021").unwrap(),
+    ///     Reading::NaiveDate(NaiveDate::from_ymd_opt(2021, 5, 1).unwrap()),
+    /// );
+    /// assert_eq!(
+    ///     parse.parse_naive("2021-05-14 18:51 PDT").unwrap(),
+    ///     Reading::Offset(DateTime::parse_from_rfc3339("2021-05-14T18:51:00-07:00").unwrap()),
+    /// );
+    /// ```
+    pub fn parse_naive(&self, input: &str) -> Result<Reading> {
+        let wall = Wall::default();
+        let parse = Parse {
+            tz: &wall,
+            default_time: self.default_time,
+            prefer_dmy: self.prefer_dmy,
+            reference: Some(self.now()),
+            two_digit_year: self.two_digit_year,
+            partial_fill: self.partial_fill,
+            fiscal_year_start: self.fiscal_year_start,
+            leap_second: self.leap_second,
+            custom: self.custom.clone(),
         };
-
-        if !re.is_match(input) {
-            return None;
-        }
-        self.rfc3339(input)
-            .or_else(|| self.ymd_hms(input))
-            .or_else(|| self.ymd_hms_z(input))
-            .or_else(|| self.ymd(input))
-            .or_else(|| self.ymd_z(input))
+        let parsed = parse.parse_fixed_offset(input)?;
+        // a format that localized an input it then rejected leaves a stale local time behind, so
+        // only take one that the result was actually made from, after moving a 24:00 time forward
+        let lexed = lexer::lex(input.as_bytes());
+        let step = lexed.end_of_day();
+        let local = match wall.local.get().map(|local| local.and_utc().timestamp()) {
+            Some(local) if local == parsed.timestamp() || local + step == parsed.timestamp() => {
+                parsed.naive_utc()
+            }
+            _ => return Ok(Reading::Offset(parsed)),
+        };
+        // a date family can also take a time of day such as noon for a timezone, so only an
+        // input it reads the same way is a date
+        let date_only = match parse.partial_family(&lexed) {
+            Some(parsed) => parsed.is_ok(),
+            None => matches!(parse.date_family(&lexed), Some(Ok(date)) if date == parsed),
+        };
+        Ok(if date_only {
+            Reading::NaiveDate(local.date())
+        } else {
+            Reading::Naive(local)
+        })
     }
 
+    /// Like [`Parse::parse`], but keeps the offset the input was written with, such as the
+    /// `-07:00` of `2021-05-14 18:51 PDT`, to display it in the sender's local time. An input
+    /// without an offset gets the offset of the timezone of `self` at that time, and a unix
+    /// timestamp is UTC.
+    ///
+    /// ```
+    /// use chrono::prelude::*;
+    /// use qsv_dateparser::datetime::Parse;
+    ///
+    /// let parse = Parse::new(&Utc, NaiveTime::MIN);
+    /// let parsed = parse.parse_fixed_offset("2021-05-14 18:51 PDT").unwrap();
+    /// assert_eq!(parsed.to_rfc3339(), "2021-05-14T18:51:00-07:00");
+    /// ```
     #[inline]
-    fn month_mdy_family(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {
-            r"^[a-zA-Z]{3,9}\.?\s+[0-9]{1,2}"
-        };
+    pub fn parse_fixed_offset(&self, input: &str) -> Result<DateTime<FixedOffset>> {
+        self.parse_hinted(input.as_bytes(), &mut None)
+            .unwrap_or_else(|| Err(anyhow!("{} did not match any formats.", input)))
+    }
 
-        if !re.is_match(input) {
-            return None;
-        }
-        self.month_mdy_hms(input)
-            .or_else(|| self.month_mdy_hms_z(input))
-            .or_else(|| self.month_mdy(input))
+    /// Like [`Parse::parse`], but also returns a short description of the family of formats that
+    /// matched, such as `yyyy-mm-dd` or `unix timestamp`, which is handy to find out why an input
+    /// was read the way it was.
+    ///
+    /// ```
+    /// use chrono::prelude::*;
+    /// use qsv_dateparser::datetime::Parse;
+    ///
+    /// let parse = Parse::new(&Utc, NaiveTime::MIN);
+    /// let (parsed, family) = parse.explain("May 8, 2009 5:57:51 PM").unwrap();
+    /// assert_eq!(parsed, Utc.with_ymd_and_hms(2009, 5, 8, 17, 57, 51).unwrap());
+    /// assert_eq!(family, "Mon dd, yyyy");
+    /// ```
+    pub fn explain(&self, input: &str) -> Result<(DateTime<Utc>, &'static str)> {
+        let (parsed, family) = self.parse_family(input)?;
+        // every format belongs to a family, so a parsed input always has one
+        let family = family.map_or("unknown", Family::name);
+        Ok((parsed.with_timezone(&Utc), family))
     }
 
     #[inline]
-    fn month_dmy_family(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[0-9]{1,2}\s+[a-zA-Z]{3,9}"
-        };
+    fn parse_family(&self, input: &str) -> Result<(DateTime<FixedOffset>, Option<Family>)> {
+        let mut hint = None;
+        let parsed = self
+            .parse_hinted(input.as_bytes(), &mut hint)
+            .unwrap_or_else(|| Err(anyhow!("{} did not match any formats.", input)))?;
+        Ok((parsed, hint))
+    }
 
-        if !re.is_match(input) {
-            return None;
-        }
-        self.month_dmy_hms(input).or_else(|| self.month_dmy(input))
+    /// Returns the strftime pattern the input is written in, such as `%Y-%m-%d %H:%M:%S %:z`,
+    /// which chrono's `parse_from_str` reads the input with and `format` writes other values
+    /// with, to pin a column to the format of its first value. A unix timestamp is `%s`.
+    ///
+    /// `None` when the input doesn't parse, or when it is written in a way strftime has no
+    /// equivalent for, such as `2021-05-14 noon`, `Q1 2021` or `2:30 p.m.`.
+    ///
+    /// ```
+    /// use chrono::prelude::*;
+    /// use qsv_dateparser::datetime::Parse;
+    ///
+    /// let parse = Parse::new(&Utc, NaiveTime::MIN);
+    /// assert_eq!(
+    ///     parse.detect_format("May 8, 2009 5:57:51 PM").as_deref(),
+    ///     Some("%b %-d, %Y %-I:%M:%S %p"),
+    /// );
+    /// assert_eq!(parse.detect_format("31/07/2021").as_deref(), Some("%d/%m/%Y"));
+    /// ```
+    pub fn detect_format(&self, input: &str) -> Option<String> {
+        let (parsed, family) = self.parse_family(input).ok()?;
+        self.template(input, parsed, family)
+            .map(|template| format::strftime(&template.fields))
     }
 
-    #[inline]
-    fn slash_mdy_family(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[0-9]{1,2}/[0-9]{1,2}"
-        };
-        if !re.is_match(input) {
-            return None;
-        }
-        if self.prefer_dmy {
-            self.slash_dmy_hms(input)
-                .or_else(|| self.slash_dmy(input))
-                .or_else(|| self.slash_mdy_hms(input))
-                .or_else(|| self.slash_mdy(input))
-        } else {
-            self.slash_mdy_hms(input)
-                .or_else(|| self.slash_mdy(input))
-                .or_else(|| self.slash_dmy_hms(input))
-                .or_else(|| self.slash_dmy(input))
-        }
+    /// Parses the input like [`Parse::parse_fixed_offset`], and also captures how it was written
+    /// as a [`DateTemplate`], to write other datetimes in the same style, such as the parsed value
+    /// shifted by an offset or converted to another timezone. The template renders the parsed
+    /// value itself as the input.
+    ///
+    /// The template is `None` when the input is written in a way it can't reproduce, as with
+    /// [`Parse::detect_format`].
+    ///
+    /// ```
+    /// use chrono::prelude::*;
+    /// use qsv_dateparser::datetime::Parse;
+    ///
+    /// let parse = Parse::new(&Utc, NaiveTime::MIN);
+    /// let (parsed, template) = parse.parse_with_template("MAY 14, 2021 6:51 pm").unwrap();
+    /// let template = template.unwrap();
+    /// let shifted = parsed + chrono::Duration::days(30);
+    /// assert_eq!(template.render(&shifted), "JUN 13, 2021 6:51 pm");
+    /// ```
+    pub fn parse_with_template(
+        &self,
+        input: &str,
+    ) -> Result<(DateTime<FixedOffset>, Option<DateTemplate>)> {
+        let (parsed, family) = self.parse_family(input)?;
+        let template = self.template(input, parsed, family);
+        Ok((parsed, template))
     }
 
-    #[inline]
-    fn slash_ymd_family(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[0-9]{4}/[0-9]{1,2}"};
-        if !re.is_match(input) {
+    fn template(
+        &self,
+        input: &str,
+        parsed: DateTime<FixedOffset>,
+        family: Option<Family>,
+    ) -> Option<DateTemplate> {
+        if family == Some(Family::UnixTimestamp) {
+            return format::timestamp(input, parsed).map(|fields| DateTemplate {
+                fields,
+                offset: Some(0),
+            });
+        }
+        let lexed = lexer::lex(input.as_bytes());
+        if let Some((_, Precision::Quarter | Precision::HalfYear)) = self.partial_date(&lexed) {
             return None;
         }
-        self.slash_ymd_hms(input).or_else(|| self.slash_ymd(input))
+        // a day and month that are equal were read in the order of the preference
+        let dmy = self.prefer_dmy && family == Some(Family::SlashMdy);
+        let expected = match self.parse_naive(input).ok()? {
+            Reading::Naive(local) => format::Expected {
+                local,
+                offset: None,
+                has_time: true,
+                dmy,
+            },
+            Reading::NaiveDate(date) => format::Expected {
+                local: date.and_time(NaiveTime::MIN),
+                offset: None,
+                has_time: false,
+                dmy,
+            },
+            Reading::Offset(datetime) => format::Expected {
+                local: datetime.naive_local(),
+                offset: Some(datetime.offset().local_minus_utc()),
+                // a date with an offset, such as 2021-02-21 PST, is at the default time
+                has_time: !matches!(self.date_family(&lexed), Some(Ok(date)) if date == datetime),
+                dmy,
+            },
+        };
+        let fields = format::fields(input, &expected)?;
+        format::matches(input, &format::strftime(&fields), &expected).then_some(DateTemplate {
+            fields,
+            offset: expected.offset,
+        })
     }
 
-    // unix timestamp
-    // - 0
-    // - -770172300
-    // - 1671673426.123456789
+    // Parses the input, trying the `hint` family first and updating it to the family that
+    // parsed the input. `None` means no format matched.
     #[inline]
-    fn unix_timestamp(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let Ok(ts_sec_val) = input.parse::<f64>() else {
-            return None;
+    fn parse_hinted(
+        &self,
+        input: &[u8],
+        hint: &mut Option<Family>,
+    ) -> Option<Result<DateTime<FixedOffset>>> {
+        let parsed = match self.custom(input, |priority| priority > 0) {
+            Some(Ok(parsed)) => {
+                *hint = Some(Family::Custom);
+                Ok(parsed)
+            }
+            _ => match self.accepted(input, hint) {
+                Some(Ok(parsed)) => Ok(parsed),
+                accepted => match self.custom(input, |priority| priority <= 0) {
+                    Some(parsed) => {
+                        if parsed.is_ok() {
+                            *hint = Some(Family::Custom);
+                        }
+                        parsed
+                    }
+                    None => accepted?,
+                },
+            },
         };
+        let parsed = match parsed {
+            Ok(parsed) if parsed.nanosecond() >= 1_000_000_000 => parsed,
+            parsed => return Some(parsed),
+        };
+        let input = String::from_utf8_lossy(input);
+        Some(match self.leap_second {
+            LeapSecond::Preserve => Ok(parsed),
+            LeapSecond::Clamp => parsed
+                .with_nanosecond(999_999_999)
+                .ok_or_else(|| anyhow!("{} is out of range.", input)),
+            LeapSecond::Reject => Err(anyhow!("{} is a leap second.", input)),
+        })
+    }
 
-        // convert the timestamp seconds value to nanoseconds
-        let ts_ns_val = ts_sec_val * 1_000_000_000_f64;
+    // Parses the input with the accepted formats, reading the time of day in the other spellings
+    // only when the input has a hint of one, so inputs that use `hh:mm[:ss]` don't pay for them.
+    #[inline]
+    fn accepted(
+        &self,
+        input: &[u8],
+        hint: &mut Option<Family>,
+    ) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut lexed = lexer::lex(input);
+        // a zone-like word such as "noon" would otherwise be consumed as an unknown timezone
+        if lexed.time_word {
+            lexed.spelled = true;
+            if let Some(Ok(parsed)) = self.detect(&lexed, hint) {
+                return Some(Ok(parsed));
+            }
+            lexed.spelled = false;
+        }
 
-        let result = Utc.timestamp_nanos(ts_ns_val as i64).with_timezone(&Utc);
-        Some(Ok(result))
+        match self.detect(&lexed, hint) {
+            Some(Ok(parsed)) => Some(Ok(parsed)),
+            parsed if !lexed.time_hint || lexed.time_word => parsed,
+            parsed => {
+                lexed.spelled = true;
+                match self.detect(&lexed, hint) {
+                    Some(Ok(spelled)) => Some(Ok(spelled)),
+                    _ => parsed,
+                }
+            }
+        }
     }
 
-    // rfc3339
-    // - 2021-05-01T01:17:02.604456Z
-    // - 2017-11-25T22:34:50Z
+    /// Like [`Parse::parse`], but takes the raw bytes of a field, such as the ones a CSV reader
+    /// hands out, and fails when they aren't valid UTF-8. The accepted formats are read from the
+    /// bytes as they are, so only input that isn't ASCII is checked to be UTF-8 first.
     #[inline]
-    fn rfc3339(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        DateTime::parse_from_rfc3339(input)
-            .ok()
+    pub fn parse_bytes(&self, input: &[u8]) -> Result<DateTime<Utc>> {
+        let text = || String::from_utf8_lossy(input);
+        if !input.is_ascii() && std::str::from_utf8(input).is_err() {
+            return Err(anyhow!("{} is not valid UTF-8.", text()));
+        }
+        self.parse_hinted(input, &mut None)
+            .unwrap_or_else(|| Err(anyhow!("{} did not match any formats.", text())))
             .map(|parsed| parsed.with_timezone(&Utc))
-            .map(Ok)
     }
 
-    // rfc2822
-    // - Wed, 02 Jun 2021 06:31:39 GMT
-    #[inline]
-    fn rfc2822(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        DateTime::parse_from_rfc2822(input)
-            .ok()
-            .map(|parsed| parsed.with_timezone(&Utc))
-            .map(Ok)
+    /// Parses each input into `out`, in order, until either runs out, and returns how many parsed.
+    /// Inputs that don't parse are `None`.
+    ///
+    /// This is meant for a column of values: the clock is read once for the whole batch, and the
+    /// family of formats that parsed the previous input is tried first. That only saves time: each
+    /// input is read the way [`Parse::parse`] reads it, so an input that more than one format
+    /// accepts, such as `2021` (a year or a unix timestamp), is read the same wherever it is.
+    pub fn parse_many<I>(&self, inputs: I, out: &mut [Option<DateTime<Utc>>]) -> usize
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut valid = 0;
+        self.batch(inputs, out.len(), |i, parsed| {
+            valid += usize::from(parsed.is_some());
+            out[i] = parsed;
+        });
+        valid
     }
 
-    // yyyy-mm-dd hh:mm:ss
-    // - 2014-04-26 05:24:37 PM
-    // - 2021-04-30 21:14
-    // - 2021-04-30 21:14:10
-    // - 2021-04-30 21:14:10.052282
-    // - 2014-04-26 17:24:37.123
-    // - 2014-04-26 17:24:37.3186369
-    // - 2012-08-03 18:31:59.257000000
-    #[inline]
-    fn ymd_hms(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {
-                r"^[0-9]{4}-[0-9]{2}-[0-9]{2}\s+[0-9]{2}:[0-9]{2}(:[0-9]{2})?(\.[0-9]{1,9})?\s*(am|pm|AM|PM)?$"
+    /// Like [`Parse::parse_many`], but writes nanoseconds since the unix epoch into `values`
+    /// and sets the matching bit of `validity` (least significant bit first, as in Apache Arrow)
+    /// for each input that parsed. The value of an input that didn't parse, or that is out of
+    /// range for nanoseconds, is `0` with its bit cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `validity` has fewer bits than `values` has elements.
+    pub fn parse_many_nanos<I>(&self, inputs: I, values: &mut [i64], validity: &mut [u8]) -> usize
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        assert!(
+            validity.len() * 8 >= values.len(),
+            "validity bitmap is too short"
+        );
+        let mut valid = 0;
+        self.batch(inputs, values.len(), |i, parsed| {
+            let nanos = parsed.and_then(|parsed| parsed.timestamp_nanos_opt());
+            let bit = 1 << (i % 8);
+            if nanos.is_some() {
+                validity[i / 8] |= bit;
+                valid += 1;
+            } else {
+                validity[i / 8] &= !bit;
+            }
+            values[i] = nanos.unwrap_or_default();
+        });
+        valid
+    }
 
-        };
-        if !re.is_match(input) {
-            return None;
-        }
+    /// Like [`Parse::parse_many`], but splits the batch across the rayon thread pool. Each chunk
+    /// keeps its own record of the last family that parsed, which doesn't change the results.
+    #[cfg(feature = "rayon")]
+    pub fn par_parse_many<S>(&self, inputs: &[S], out: &mut [Option<DateTime<Utc>>]) -> usize
+    where
+        S: AsRef<str> + Sync,
+        Tz2: Sync,
+    {
+        use rayon::prelude::*;
 
-        self.tz
-            .datetime_from_str(input, "%Y-%m-%d %H:%M:%S")
-            .or_else(|_| self.tz.datetime_from_str(input, "%Y-%m-%d %H:%M"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%Y-%m-%d %H:%M:%S%.f"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%Y-%m-%d %I:%M:%S %P"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%Y-%m-%d %I:%M %P"))
-            .ok()
-            .map(|parsed| parsed.with_timezone(&Utc))
-            .map(Ok)
+        let parse = self.pinned();
+        inputs
+            .par_chunks(CHUNK)
+            .zip(out.par_chunks_mut(CHUNK))
+            .map(|(inputs, out)| parse.parse_many(inputs, out))
+            .sum()
     }
 
-    // yyyy-mm-dd hh:mm:ss z
-    // - 2017-11-25 13:31:15 PST
-    // - 2017-11-25 13:31 PST
-    // - 2014-12-16 06:20:00 UTC
-    // - 2014-12-16 06:20:00 GMT
-    // - 2014-04-26 13:13:43 +0800
-    // - 2014-04-26 13:13:44 +09:00
-    // - 2012-08-03 18:31:59.257000000 +0000
-    // - 2015-09-30 18:48:56.35272715 UTC
-    #[inline]
-    fn ymd_hms_z(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {
-                r"^[0-9]{4}-[0-9]{2}-[0-9]{2}\s+[0-9]{2}:[0-9]{2}(:[0-9]{2})?(\.[0-9]{1,9})?(?P<tz>\s*[+-:a-zA-Z0-9]{3,6})$"
-        };
+    /// Like [`Parse::parse_many_nanos`], but splits the batch across the rayon thread pool.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `validity` has fewer bits than `values` has elements.
+    #[cfg(feature = "rayon")]
+    pub fn par_parse_many_nanos<S>(
+        &self,
+        inputs: &[S],
+        values: &mut [i64],
+        validity: &mut [u8],
+    ) -> usize
+    where
+        S: AsRef<str> + Sync,
+        Tz2: Sync,
+    {
+        use rayon::prelude::*;
 
-        if !re.is_match(input) {
-            return None;
-        }
-        if let Some(caps) = re.captures(input) {
-            if let Some(matched_tz) = caps.name("tz") {
-                let parse_from_str = NaiveDateTime::parse_from_str;
-                return match timezone::parse(matched_tz.as_str().trim()) {
-                    Ok(offset) => parse_from_str(input, "%Y-%m-%d %H:%M:%S %Z")
-                        .or_else(|_| parse_from_str(input, "%Y-%m-%d %H:%M %Z"))
-                        .or_else(|_| parse_from_str(input, "%Y-%m-%d %H:%M:%S%.f %Z"))
-                        .ok()
-                        .and_then(|parsed| offset.from_local_datetime(&parsed).single())
-                        .map(|datetime| datetime.with_timezone(&Utc))
-                        .map(Ok),
-                    Err(err) => Some(Err(err)),
-                };
-            }
-        }
-        None
+        assert!(
+            validity.len() * 8 >= values.len(),
+            "validity bitmap is too short"
+        );
+        let parse = self.pinned();
+        inputs
+            .par_chunks(CHUNK)
+            .zip(values.par_chunks_mut(CHUNK))
+            .zip(validity.par_chunks_mut(CHUNK / 8))
+            .map(|((inputs, values), validity)| parse.parse_many_nanos(inputs, values, validity))
+            .sum()
     }
 
-    // yyyy-mm-dd
-    // - 2021-02-21
+    // A copy whose reference clock is pinned to the current time, so a batch reads it once.
     #[inline]
-    fn ymd(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[0-9]{4}-[0-9]{2}-[0-9]{2}$"
-        };
-
-        if !re.is_match(input) {
-            return None;
+    fn pinned(&self) -> Self {
+        Self {
+            reference: Some(self.now()),
+            custom: self.custom.clone(),
+            ..*self
         }
-        let now = Utc::now()
-            .date()
-            .and_time(self.default_time)?
-            .with_timezone(self.tz);
-        NaiveDate::parse_from_str(input, "%Y-%m-%d")
-            .ok()
-            .map(|parsed| parsed.and_time(now.time()))
-            .and_then(|datetime| self.tz.from_local_datetime(&datetime).single())
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
     }
 
-    // yyyy-mm-dd z
-    // - 2021-02-21 PST
-    // - 2021-02-21 UTC
-    // - 2020-07-20+08:00 (yyyy-mm-dd-07:00)
     #[inline]
-    fn ymd_z(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[0-9]{4}-[0-9]{2}-[0-9]{2}(?P<tz>\s*[+-:a-zA-Z0-9]{3,6})$"
-        };
-        if !re.is_match(input) {
-            return None;
+    fn batch<I>(&self, inputs: I, len: usize, mut write: impl FnMut(usize, Option<DateTime<Utc>>))
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut batch = Batch::new(self);
+        for (i, input) in inputs.into_iter().take(len).enumerate() {
+            write(i, batch.parse(input.as_ref()).and_then(Result::ok));
         }
+    }
 
-        if let Some(caps) = re.captures(input) {
-            if let Some(matched_tz) = caps.name("tz") {
-                return match timezone::parse(matched_tz.as_str().trim()) {
-                    Ok(offset) => {
-                        let now = Utc::now()
-                            .date()
-                            .and_time(self.default_time)?
-                            .with_timezone(&offset);
-                        NaiveDate::parse_from_str(input, "%Y-%m-%d %Z")
-                            .ok()
-                            .map(|parsed| parsed.and_time(now.time()))
-                            .and_then(|datetime| offset.from_local_datetime(&datetime).single())
-                            .map(|at_tz| at_tz.with_timezone(&Utc))
-                            .map(Ok)
+    // Tries the `hint` family, and then each family that can match an input of this shape. The
+    // hint is only taken when it parses the input, and is updated to the family that did.
+    //
+    // The hint must not change the result, so it is only tried when it can match an input of
+    // this shape, and after the families before it that read some of the same inputs have passed
+    // on the input.
+    #[inline]
+    fn detect(
+        &self,
+        input: &Lexed<'_>,
+        hint: &mut Option<Family>,
+    ) -> Option<Result<DateTime<FixedOffset>>> {
+        let candidates = Family::candidates(input.shape);
+        if let Some(at) = hint.and_then(|hint| candidates.iter().position(|&c| c == hint)) {
+            let before = candidates[..at]
+                .iter()
+                .filter(|family| family.overlaps())
+                .find_map(|&family| self.family(family, input).map(|parsed| (family, parsed)));
+            match before {
+                Some((family, parsed)) => {
+                    if parsed.is_ok() {
+                        *hint = Some(family);
                     }
-                    Err(err) => Some(Err(err)),
-                };
+                    return Some(parsed);
+                }
+                None => {
+                    if let Some(Ok(parsed)) = self.family(candidates[at], input) {
+                        return Some(Ok(parsed));
+                    }
+                }
+            }
+        }
+        for &family in candidates {
+            if let Some(parsed) = self.family(family, input) {
+                if parsed.is_ok() {
+                    *hint = Some(family);
+                }
+                return Some(parsed);
             }
         }
         None
     }
 
-    // yyyy-mon-dd
-    // - 2021-Feb-21
     #[inline]
-    fn month_ymd(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[0-9]{4}-[a-zA-Z]{3,9}-[0-9]{2}$"
-        };
-        if !re.is_match(input) {
-            return None;
+    fn family(&self, family: Family, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        match family {
+            Family::HttpDate => self.http_date_family(input),
+            Family::Partial => self.partial_datetime(input),
+            Family::UnixTimestamp => self.unix_timestamp(input),
+            Family::SlashMdy => self.slash_mdy_family(input),
+            Family::SlashYmd => self.slash_ymd_family(input),
+            Family::Ymd => self.ymd_family(input),
+            Family::MonthYmd => self.month_ymd(input),
+            Family::MonthMdy => self.month_mdy_family(input),
+            Family::MonthDmy => self.month_dmy_family(input),
+            Family::UnixDate => self.unix_date(input),
+            Family::Clf => self.clf(input),
+            Family::Custom => self.custom(input.input(), |_| true),
         }
-
-        let now = Utc::now()
-            .date()
-            .and_time(self.default_time)?
-            .with_timezone(self.tz);
-        NaiveDate::parse_from_str(input, "%Y-%m-%d")
-            .or_else(|_| NaiveDate::parse_from_str(input, "%Y-%b-%d"))
-            .ok()
-            .map(|parsed| parsed.and_time(now.time()))
-            .and_then(|datetime| self.tz.from_local_datetime(&datetime).single())
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
     }
 
-    // Mon dd, yyyy, hh:mm:ss
-    // - May 8, 2009 5:57:51 PM
-    // - September 17, 2012 10:09am
-    // - September 17, 2012, 10:10:09
+    /// Like [`Parse::parse`], but also returns the [`Precision`] of the input, so a partial date
+    /// such as `2021` is not mistaken for the precise instant it was filled in to.
     #[inline]
-    fn month_mdy_hms(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {
-                r"^[a-zA-Z]{3,9}\.?\s+[0-9]{1,2},\s+[0-9]{2,4},?\s+[0-9]{1,2}:[0-9]{2}(:[0-9]{2})?\s*(am|pm|AM|PM)?$"
-        };
-        if !re.is_match(input) {
-            return None;
+    pub fn parse_with_precision(&self, input: &str) -> Result<(DateTime<Utc>, Precision)> {
+        let lexed = lexer::lex(input.as_bytes());
+        if let Some(parsed) = self.partial_family(&lexed) {
+            return parsed.map(|(datetime, precision)| (datetime.with_timezone(&Utc), precision));
         }
-
-        let dt = input.replace(", ", " ").replace(". ", " ");
-        self.tz
-            .datetime_from_str(&dt, "%B %d %Y %H:%M:%S")
-            .or_else(|_| self.tz.datetime_from_str(&dt, "%B %d %Y %H:%M"))
-            .or_else(|_| self.tz.datetime_from_str(&dt, "%B %d %Y %I:%M:%S %P"))
-            .or_else(|_| self.tz.datetime_from_str(&dt, "%B %d %Y %I:%M %P"))
-            .ok()
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
+        let datetime = self.parse(input)?;
+        let precision = match self.date_family(&lexed) {
+            Some(Ok(_)) => Precision::Day,
+            _ => Precision::Time,
+        };
+        Ok((datetime, precision))
     }
 
-    // Mon dd, yyyy hh:mm:ss z
-    // - May 02, 2021 15:51:31 UTC
-    // - May 02, 2021 15:51 UTC
-    // - May 26, 2021, 12:49 AM PDT
-    // - September 17, 2012 at 10:09am PST
+    // the members of the other families that only have a date and fill in the time of day
+    #[inline]
+    fn date_family(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let slash = || {
+            if self.prefer_dmy {
+                self.slash_dmy(input).or_else(|| self.slash_mdy(input))
+            } else {
+                self.slash_mdy(input).or_else(|| self.slash_dmy(input))
+            }
+        };
+        slash()
+            .or_else(|| self.slash_ymd(input))
+            .or_else(|| self.ymd(input))
+            .or_else(|| self.ymd_z(input))
+            .or_else(|| self.month_ymd(input))
+            .or_else(|| self.month_mdy(input))
+            .or_else(|| self.month_dmy(input))
+    }
+
+    #[inline]
+    fn partial_datetime(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        self.partial_family(input)
+            .map(|parsed| parsed.map(|(datetime, _)| datetime))
+    }
+
+    #[inline]
+    fn partial_family(
+        &self,
+        input: &Lexed<'_>,
+    ) -> Option<Result<(DateTime<FixedOffset>, Precision)>> {
+        let (start, precision) = self.partial_date(input)?;
+        let datetime = match self.partial_fill {
+            PartialFill::Start => start.and_time(NaiveTime::MIN),
+            PartialFill::End => precision
+                .period_end(start)?
+                .and_time(NaiveTime::MIN)
+                .checked_sub_signed(chrono::Duration::nanoseconds(1))?,
+            PartialFill::Reject => {
+                return Some(Err(anyhow!(
+                    "{} is a partial date.",
+                    String::from_utf8_lossy(input.input())
+                )))
+            }
+        };
+        self.tz
+            .from_local_datetime(&datetime)
+            .single()
+            .map(|at_tz| Ok((at_tz.fixed_offset(), precision)))
+    }
+
+    // the first day and the precision of a partial date
+    #[inline]
+    fn partial_date(&self, input: &Lexed<'_>) -> Option<(NaiveDate, Precision)> {
+        self.year(input)
+            .or_else(|| self.year_month(input))
+            .or_else(|| self.month_year(input))
+            .or_else(|| self.month_day(input))
+            .or_else(|| self.quarter(input))
+    }
+
+    /// Parse the input into the half-open interval `[start, end)` that it covers. Partial dates
+    /// cover their whole period, e.g. `2021-05` is `[2021-05-01, 2021-06-01)`, and dates without a
+    /// time cover the whole day. Explicit ranges are also accepted, with each endpoint parsed like
+    /// any other input:
+    ///
+    /// - `2021-05-01 to 2021-05-31`
+    /// - `2021-05-01/2021-05-31` (ISO 8601 interval)
+    /// - `May 1-31, 2021` (also with an en dash)
+    ///
+    /// An input with a time, such as a unix timestamp, is the empty interval `[t, t)`.
+    pub fn parse_interval(&self, input: &str) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+        self.range(input).unwrap_or_else(|| self.period(input))
+    }
+
+    // the interval covered by a single date
+    #[inline]
+    fn period(&self, input: &str) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let lexed = lexer::lex(input.as_bytes());
+        let (start, precision) = match self.partial_date(&lexed) {
+            Some(partial) => partial,
+            None => match self.date_family(&lexed) {
+                Some(Ok(datetime)) => {
+                    (datetime.with_timezone(self.tz).date_naive(), Precision::Day)
+                }
+                _ => {
+                    let datetime = self.parse(input)?;
+                    return Ok((datetime, datetime));
+                }
+            },
+        };
+        self.span(input, start, precision)
+    }
+
+    // the interval from the local midnight that starts the period to the one that ends it
+    #[inline]
+    fn span(
+        &self,
+        input: &str,
+        start: NaiveDate,
+        precision: Precision,
+    ) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let end = precision
+            .period_end(start)
+            .ok_or_else(|| anyhow!("{} is out of range.", input))?;
+        let midnight = |date: NaiveDate| {
+            self.tz
+                .from_local_datetime(&date.and_time(NaiveTime::MIN))
+                .single()
+                .map(|at_tz| at_tz.with_timezone(&Utc))
+                .ok_or_else(|| anyhow!("{} has no local midnight.", date))
+        };
+        Ok((midnight(start)?, midnight(end)?))
+    }
+
+    // explicit ranges
+    // - 2021-05-01 to 2021-05-31
+    // - 2021-05-01/2021-05-31
+    // - 2021-05-01 - 2021-05-31
+    // - May 1-31, 2021
+    // - May 1–31, 2021
     #[inline]
-    fn month_mdy_hms_z(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {
-                r"^[a-zA-Z]{3,9}\s+[0-9]{1,2},?\s+[0-9]{4}\s*,?(at)?\s+[0-9]{2}:[0-9]{2}(:[0-9]{2})?\s*(am|pm|AM|PM)?(?P<tz>\s+[+-:a-zA-Z0-9]{3,6})$",
+    fn range(&self, input: &str) -> Option<Result<(DateTime<Utc>, DateTime<Utc>)>> {
+        let (first, last) = match Self::month_days(input) {
+            Some((first, last)) => (
+                self.span(input, first, Precision::Day),
+                self.span(input, last, Precision::Day),
+            ),
+            None => {
+                let (first, last) = input
+                    .split_once(" to ")
+                    .or_else(|| input.split_once(" – "))
+                    .or_else(|| {
+                        (!Self::offset_after_time(input)).then(|| input.split_once(" - "))?
+                    })
+                    .or_else(|| Self::iso_interval(input).then(|| input.split_once('/'))?)?;
+                (self.period(first.trim()), self.period(last.trim()))
+            }
         };
-        if !re.is_match(input) {
+
+        // only a range when both ends parse on their own, or else the whole input is one value
+        let (Ok((start, _)), Ok((_, end))) = (first, last) else {
             return None;
+        };
+        if end < start {
+            return Some(Err(anyhow!("{} ends before it starts.", input)));
         }
+        Some(Ok((start, end)))
+    }
 
-        if let Some(caps) = re.captures(input) {
-            if let Some(matched_tz) = caps.name("tz") {
-                let parse_from_str = NaiveDateTime::parse_from_str;
-                return match timezone::parse(matched_tz.as_str().trim()) {
-                    Ok(offset) => {
-                        let dt = input.replace(',', "").replace("at", "");
-                        parse_from_str(&dt, "%B %d %Y %H:%M:%S %Z")
-                            .or_else(|_| parse_from_str(&dt, "%B %d %Y %H:%M %Z"))
-                            .or_else(|_| parse_from_str(&dt, "%B %d %Y %I:%M:%S %P %Z"))
-                            .or_else(|_| parse_from_str(&dt, "%B %d %Y %I:%M %P %Z"))
-                            .ok()
-                            .and_then(|parsed| offset.from_local_datetime(&parsed).single())
-                            .map(|datetime| datetime.with_timezone(&Utc))
-                            .map(Ok)
-                    }
-                    Err(err) => Some(Err(err)),
-                };
+    // The first and last day of a range of days in a month, such as `May 1-31, 2021`.
+    #[inline]
+    fn month_days(input: &str) -> Option<(NaiveDate, NaiveDate)> {
+        let lexed = lexer::lex(input.as_bytes());
+        let mut cursor = lexed.cursor();
+        let month = cursor.month(3..=9)?;
+        cursor.punct(b'.');
+        cursor.space()?;
+        let first = cursor.number(1..=2)?;
+        cursor.space();
+        cursor.dash()?;
+        cursor.space();
+        let last = cursor.number(1..=2)?;
+        cursor.punct(b',')?;
+        cursor.space()?;
+        let year = cursor.number(4..=4)? as i32;
+        cursor.end()?;
+        NaiveDate::from_ymd_opt(year, month, first).zip(NaiveDate::from_ymd_opt(year, month, last))
+    }
+
+    // Whether the input ends in a time of day and an offset written with a dash, as the
+    // ` - 0700` of `2021-05-14 18:51 - 0700`, which isn't the dash of a range.
+    #[inline]
+    fn offset_after_time(input: &str) -> bool {
+        match input.as_bytes() {
+            [.., h, b':', m1, m2, b' ', b'-', b' ', o1, o2, b':', o3, o4]
+            | [.., h, b':', m1, m2, b' ', b'-', b' ', o1, o2, o3, o4] => {
+                [h, m1, m2, o1, o2, o3, o4]
+                    .iter()
+                    .all(|c| c.is_ascii_digit())
             }
+            _ => false,
         }
-        None
     }
 
-    // Mon dd, yyyy
-    // - May 25, 2021
-    // - oct 7, 1970
-    // - oct 7, 70
-    // - oct. 7, 1970
-    // - oct. 7, 70
-    // - October 7, 1970
+    // Whether the input is two ISO 8601 dates split by a slash, as `2021-05-01/2021-05-31`.
     #[inline]
-    fn month_mdy(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[a-zA-Z]{3,9}\.?\s+[0-9]{1,2},\s+[0-9]{2,4}$"
+    fn iso_interval(input: &str) -> bool {
+        let year = |bytes: &[u8]| matches!(bytes, [y1, y2, y3, y4, b'-', ..] if [y1, y2, y3, y4].iter().all(|c| c.is_ascii_digit()));
+        let bytes = input.as_bytes();
+        let Some(slash) = bytes.iter().position(|&c| c == b'/') else {
+            return false;
         };
-        if !re.is_match(input) {
-            return None;
+        year(bytes)
+            && year(&bytes[slash + 1..])
+            && bytes[5..slash]
+                .iter()
+                .all(|&c| c.is_ascii_alphanumeric() || b"-+:. ".contains(&c))
+    }
+
+    // HTTP-date (RFC 9110), which recipients must accept in all three forms:
+    // IMF-fixdate (a subset of rfc2822), obsolete RFC 850 and asctime.
+    #[inline]
+    fn http_date_family(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        self.rfc2822(input)
+            .or_else(|| self.rfc850(input))
+            .or_else(|| self.asctime(input))
+    }
+
+    #[inline]
+    fn ymd_family(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        self.rfc3339(input)
+            .or_else(|| self.ymd_hms(input))
+            .or_else(|| self.ymd_hms_z(input))
+            .or_else(|| self.ymd(input))
+            .or_else(|| self.ymd_z(input))
+    }
+
+    #[inline]
+    fn month_mdy_family(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        self.month_mdy_hms(input)
+            .or_else(|| self.month_mdy_hms_z(input))
+            .or_else(|| self.month_mdy(input))
+            .or_else(|| self.month_md_hms(input))
+    }
+
+    #[inline]
+    fn month_dmy_family(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        self.month_dmy_hms(input).or_else(|| self.month_dmy(input))
+    }
+
+    #[inline]
+    fn slash_mdy_family(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        if self.prefer_dmy {
+            self.slash_dmy_hms(input)
+                .or_else(|| self.slash_dmy(input))
+                .or_else(|| self.slash_mdy_hms(input))
+                .or_else(|| self.slash_mdy(input))
+        } else {
+            self.slash_mdy_hms(input)
+                .or_else(|| self.slash_mdy(input))
+                .or_else(|| self.slash_dmy_hms(input))
+                .or_else(|| self.slash_dmy(input))
         }
+    }
 
-        let now = Utc::now()
-            .date()
-            .and_time(self.default_time)?
-            .with_timezone(self.tz);
-        let dt = input.replace(", ", " ").replace(". ", " ");
-        NaiveDate::parse_from_str(&dt, "%B %d %y")
-            .or_else(|_| NaiveDate::parse_from_str(&dt, "%B %d %Y"))
-            .ok()
-            .map(|parsed| parsed.and_time(now.time()))
-            .and_then(|datetime| self.tz.from_local_datetime(&datetime).single())
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
+    #[inline]
+    fn slash_ymd_family(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        self.slash_ymd_hms(input).or_else(|| self.slash_ymd(input))
     }
 
-    // dd Mon yyyy hh:mm:ss
-    // - 12 Feb 2006, 19:17
-    // - 12 Feb 2006 19:17
-    // - 14 May 2019 19:11:40.164
     #[inline]
-    fn month_dmy_hms(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {
-                r"^[0-9]{1,2}\s+[a-zA-Z]{3,9}\s+[0-9]{2,4},?\s+[0-9]{1,2}:[0-9]{2}(:[0-9]{2})?(\.[0-9]{1,9})?$"
-        };
-        if !re.is_match(input) {
-            return None;
+    fn now(&self) -> DateTime<Utc> {
+        self.reference.unwrap_or_else(Utc::now)
+    }
+
+    #[inline]
+    fn expand_year(&self, parsed: &mut Parsed) -> Option<()> {
+        if let (None, Some(yy)) = (parsed.year(), parsed.year_mod_100()) {
+            let year = self.two_digit_year.expand(yy, self.now().year())?;
+            parsed.set_year(i64::from(year)).ok()?;
         }
+        Some(())
+    }
 
-        let dt = input.replace(", ", " ");
-        self.tz
-            .datetime_from_str(&dt, "%d %B %Y %H:%M:%S")
-            .or_else(|_| self.tz.datetime_from_str(&dt, "%d %B %Y %H:%M"))
-            .or_else(|_| self.tz.datetime_from_str(&dt, "%d %B %Y %H:%M:%S%.f"))
-            .or_else(|_| self.tz.datetime_from_str(&dt, "%d %B %Y %I:%M:%S %P"))
-            .or_else(|_| self.tz.datetime_from_str(&dt, "%d %B %Y %I:%M %P"))
-            .ok()
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
+    // A year as written, with a two-digit one expanded by the [`TwoDigitYear`] policy, which
+    // rejects the input when it rejects the year.
+    #[inline]
+    fn full_year(&self, (year, two_digit): (i32, bool)) -> Option<i32> {
+        if two_digit {
+            self.two_digit_year.expand(year, self.now().year())
+        } else {
+            Some(year)
+        }
     }
 
-    // dd Mon yyyy
-    // - 7 oct 70
-    // - 7 oct 1970
-    // - 03 February 2013
-    // - 1 July 2013
+    // The local date and time in `tz`, moved forward by the step of a 24:00 time.
     #[inline]
-    fn month_dmy(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[0-9]{1,2}\s+[a-zA-Z]{3,9}\s+[0-9]{2,4}$"
-        };
-        if !re.is_match(input) {
-            return None;
+    fn localize<Tz: TimeZone>(
+        tz: &Tz,
+        local: NaiveDateTime,
+        step: i64,
+    ) -> Option<DateTime<FixedOffset>> {
+        tz.from_local_datetime(&local)
+            .single()
+            .map(|at_tz| at_tz.fixed_offset() + chrono::Duration::seconds(step))
+    }
+
+    // A date without a time of day in `tz`, at the default time, which is a time of day in UTC
+    // on the clock of `tz`.
+    #[inline]
+    fn at_default_time<Tz: TimeZone>(
+        &self,
+        tz: &Tz,
+        date: NaiveDate,
+    ) -> Option<DateTime<FixedOffset>> {
+        let now = self.now().date().and_time(self.default_time)?;
+        Self::localize(tz, date.and_time(now.with_timezone(tz).time()), 0)
+    }
+
+    // A timezone abbreviation or offset, as read by `Cursor::zone`.
+    #[inline]
+    fn offset(zone: &[u8]) -> Result<FixedOffset> {
+        timezone::parse(&String::from_utf8_lossy(zone))
+    }
+
+    // + or -, as 1 or -1
+    #[inline]
+    fn sign(cursor: &mut Cursor<'_, '_>) -> Option<i32> {
+        if cursor.punct(b'+').is_some() {
+            Some(1)
+        } else {
+            cursor.punct(b'-').map(|()| -1)
         }
+    }
 
-        let now = Utc::now()
-            .date()
-            .and_time(self.default_time)?
-            .with_timezone(self.tz);
-        NaiveDate::parse_from_str(input, "%d %B %y")
-            .or_else(|_| NaiveDate::parse_from_str(input, "%d %B %Y"))
-            .ok()
-            .map(|parsed| parsed.and_time(now.time()))
-            .and_then(|datetime| self.tz.from_local_datetime(&datetime).single())
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
+    // yyyy-mm-dd
+    #[inline]
+    fn iso_date(cursor: &mut Cursor<'_, '_>) -> Option<NaiveDate> {
+        let year = cursor.number(4..=4)?;
+        cursor.punct(b'-')?;
+        let month = cursor.number(2..=2)?;
+        cursor.punct(b'-')?;
+        let day = cursor.number(2..=2)?;
+        NaiveDate::from_ymd_opt(year as i32, month, day)
     }
 
-    // mm/dd/yyyy hh:mm:ss
-    // - 4/8/2014 22:05
-    // - 04/08/2014 22:05
-    // - 4/8/14 22:05
-    // - 04/2/2014 03:00:51
-    // - 8/8/1965 12:00:00 AM
-    // - 8/8/1965 01:00:01 PM
-    // - 8/8/1965 01:00 PM
-    // - 8/8/1965 1:00 PM
-    // - 8/8/1965 12:00 AM
-    // - 4/02/2014 03:00:51
-    // - 03/19/2012 10:11:59
-    // - 03/19/2012 10:11:59.3186369
+    // Mon dd, yy[yy]
+    #[inline]
+    fn month_mdy_date(&self, cursor: &mut Cursor<'_, '_>) -> Option<NaiveDate> {
+        let month = cursor.month(3..=9)?;
+        cursor.punct(b'.');
+        cursor.space()?;
+        let day = cursor.number(1..=2)?;
+        cursor.punct(b',')?;
+        cursor.space()?;
+        let year = self.full_year(cursor.year()?)?;
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
+
+    // dd Mon yy[yy]
+    #[inline]
+    fn month_dmy_date(&self, cursor: &mut Cursor<'_, '_>) -> Option<NaiveDate> {
+        let day = cursor.number(1..=2)?;
+        cursor.space()?;
+        let month = cursor.month(3..=9)?;
+        cursor.space()?;
+        let year = self.full_year(cursor.year()?)?;
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
+
+    // mm/dd/yy[yy], or dd/mm/yy[yy] when `dmy`
     #[inline]
-    fn slash_mdy_hms(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {
-                r"^[0-9]{1,2}/[0-9]{1,2}/[0-9]{2,4}\s+[0-9]{1,2}:[0-9]{2}(:[0-9]{2})?(\.[0-9]{1,9})?\s*(am|pm|AM|PM)?$"
+    fn slash_date(&self, cursor: &mut Cursor<'_, '_>, dmy: bool) -> Option<NaiveDate> {
+        let first = cursor.number(1..=2)?;
+        cursor.punct(b'/')?;
+        let second = cursor.number(1..=2)?;
+        cursor.punct(b'/')?;
+        let year = self.full_year(cursor.year()?)?;
+        let (month, day) = if dmy {
+            (second, first)
+        } else {
+            (first, second)
         };
-        if !re.is_match(input) {
-            return None;
-        }
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
 
-        self.tz
-            .datetime_from_str(input, "%m/%d/%y %H:%M:%S")
-            .or_else(|_| self.tz.datetime_from_str(input, "%m/%d/%y %H:%M"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%m/%d/%y %H:%M:%S%.f"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%m/%d/%y %I:%M:%S %P"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%m/%d/%y %I:%M %P"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%m/%d/%Y %H:%M:%S"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%m/%d/%Y %H:%M"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%m/%d/%Y %H:%M:%S%.f"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%m/%d/%Y %I:%M:%S %P"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%m/%d/%Y %I:%M %P"))
-            .ok()
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
+    // yyyy/mm/dd
+    #[inline]
+    fn slash_ymd_date(cursor: &mut Cursor<'_, '_>) -> Option<NaiveDate> {
+        let year = cursor.number(4..=4)?;
+        cursor.punct(b'/')?;
+        let month = cursor.number(1..=2)?;
+        cursor.punct(b'/')?;
+        let day = cursor.number(1..=2)?;
+        NaiveDate::from_ymd_opt(year as i32, month, day)
+    }
+
+    // Day Mon dd, checking the weekday once the year is known
+    #[inline]
+    fn asctime_date(cursor: &mut Cursor<'_, '_>) -> Option<(Weekday, u32, u32)> {
+        let weekday = cursor.weekday(3..=3)?;
+        cursor.space()?;
+        let month = cursor.month(3..=3)?;
+        cursor.space()?;
+        let day = cursor.number(1..=2)?;
+        Some((weekday, month, day))
+    }
+
+    // custom formats, registered with `custom_format_with`
+    // - 14-May-2021 18h51 (%d-%b-%Y %Hh%M)
+    // - 2021.05.14-18:51:00 (%Y.%m.%d-%H:%M:%S)
+    #[inline]
+    fn custom(
+        &self,
+        input: &[u8],
+        priority: impl Fn(i32) -> bool,
+    ) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut formats = self
+            .custom
+            .as_deref()?
+            .iter()
+            .filter(|format| priority(format.priority))
+            .filter(|format| {
+                format
+                    .prefilter
+                    .as_ref()
+                    .map_or(true, |re| re.is_match(input))
+            })
+            .peekable();
+        // chrono reads the patterns from text, so the input is only decoded for one to try
+        formats.peek()?;
+        let input = std::str::from_utf8(input).ok()?;
+        formats.find_map(|format| self.custom_format_parse(format, input))
     }
 
-    // dd/mm/yyyy hh:mm:ss
-    // - 8/4/2014 22:05
-    // - 08/04/2014 22:05
-    // - 8/4/14 22:05
-    // - 2/04/2014 03:00:51
-    // - 8/8/1965 12:00:00 AM
-    // - 8/8/1965 01:00:01 PM
-    // - 8/8/1965 01:00 PM
-    // - 8/8/1965 1:00 PM
-    // - 8/8/1965 12:00 AM
-    // - 02/4/2014 03:00:51
-    // - 19/03/2012 10:11:59
-    // - 19/03/2012 10:11:59.3186369
     #[inline]
-    fn slash_dmy_hms(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {
-                r"^[0-9]{1,2}/[0-9]{1,2}/[0-9]{2,4}\s+[0-9]{1,2}:[0-9]{2}(:[0-9]{2})?(\.[0-9]{1,9})?\s*(am|pm|AM|PM)?$"
+    fn custom_format_parse(
+        &self,
+        format: &CustomFormat,
+        input: &str,
+    ) -> Option<Result<DateTime<FixedOffset>>> {
+        let items = &format.items;
+        let mut parsed = Parsed::new();
+        // chrono doesn't read timezone abbreviations, so %Z is read like in the `*_z` families
+        let offset = match format.zone {
+            Some(at) => {
+                let rest =
+                    chrono::format::parse_and_remainder(&mut parsed, input, items[..at].iter())
+                        .ok()?;
+                let end = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric() && !matches!(c, '+' | '-' | ':'))
+                    .unwrap_or(rest.len());
+                chrono::format::parse(&mut parsed, &rest[end..], items[at + 1..].iter()).ok()?;
+                match timezone::parse(&rest[..end]) {
+                    Ok(offset) => Some(offset),
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+            None => {
+                chrono::format::parse(&mut parsed, input, items.iter()).ok()?;
+                match (parsed.offset(), parsed.timestamp()) {
+                    (Some(offset), _) => Some(FixedOffset::east_opt(offset)?),
+                    // a unix timestamp is in UTC
+                    (None, Some(_)) => Some(FixedOffset::east_opt(0)?),
+                    (None, None) => None,
+                }
+            }
+        };
+        self.expand_year(&mut parsed)?;
+
+        let local = match parsed.to_naive_datetime_with_offset(0) {
+            Ok(local) => local,
+            Err(_) => {
+                let date = parsed.to_naive_date().ok()?;
+                let now = self.now().date().and_time(self.default_time)?;
+                let time = match offset {
+                    Some(offset) => now.with_timezone(&offset).time(),
+                    None => now.with_timezone(self.tz).time(),
+                };
+                date.and_time(time)
+            }
         };
-        if !re.is_match(input) {
+        match offset {
+            Some(offset) => offset.from_local_datetime(&local).single(),
+            None => self
+                .tz
+                .from_local_datetime(&local)
+                .single()
+                .map(|datetime| datetime.fixed_offset()),
+        }
+        .map(Ok)
+    }
+
+    // unix timestamp
+    // - 0
+    // - -770172300
+    // - 1671673426.123456789
+    #[inline]
+    fn unix_timestamp(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        Self::sign(&mut cursor);
+        // as f64 reads it: 1620036248, 1620036248. or .5
+        let whole = cursor.digits(1..=usize::MAX).is_some();
+        let part = cursor.punct(b'.').is_some() && cursor.digits(1..=usize::MAX).is_some();
+        if !(whole || part) {
             return None;
         }
+        cursor.end()?;
+        let Ok(ts_sec_val) = String::from_utf8_lossy(input.input()).parse::<f64>() else {
+            return None;
+        };
 
-        self.tz
-            .datetime_from_str(input, "%d/%m/%y %H:%M:%S")
-            .or_else(|_| self.tz.datetime_from_str(input, "%d/%m/%y %H:%M"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%d/%m/%y %H:%M:%S%.f"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%d/%m/%y %I:%M:%S %P"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%d/%m/%y %I:%M %P"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%d/%m/%Y %H:%M:%S"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%d/%m/%Y %H:%M"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%d/%m/%Y %H:%M:%S%.f"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%d/%m/%Y %I:%M:%S %P"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%d/%m/%Y %I:%M %P"))
-            .ok()
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
+        // convert the timestamp seconds value to nanoseconds
+        let ts_ns_val = ts_sec_val * 1_000_000_000_f64;
+
+        let result = Utc.timestamp_nanos(ts_ns_val as i64).fixed_offset();
+        Some(Ok(result))
     }
 
-    // mm/dd/yyyy
-    // - 3/31/2014
-    // - 03/31/2014
-    // - 08/21/71
-    // - 8/1/71
+    // rfc3339
+    // - 2021-05-01T01:17:02.604456Z
+    // - 2017-11-25T22:34:50Z
     #[inline]
-    fn slash_mdy(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[0-9]{1,2}/[0-9]{1,2}/[0-9]{2,4}$"
+    fn rfc3339(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = Self::iso_date(&mut cursor)?;
+        let clock = cursor.clock(Layout {
+            separator: Separator::T,
+            fraction: true,
+            ..LOG_HMS
+        })?;
+        let offset = if cursor.keyword("z").is_some() {
+            0
+        } else {
+            let sign = Self::sign(&mut cursor)?;
+            let hours = cursor.number(2..=2)?;
+            cursor.punct(b':')?;
+            let minutes = cursor.number(2..=2)?;
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            sign * (hours * 3600 + minutes * 60) as i32
         };
-        if !re.is_match(input) {
-            return None;
+        cursor.end()?;
+        Self::localize(
+            &FixedOffset::east_opt(offset)?,
+            date.and_time(clock.time),
+            clock.step,
+        )
+        .map(Ok)
+    }
+
+    // rfc2822
+    // - Wed, 02 Jun 2021 06:31:39 GMT
+    //
+    // Read as chrono reads it: a year of two or three digits is in the 1900s, or the 2000s when
+    // it is two digits below 50, and a zone name is one RFC 2822 has, or a military zone.
+    #[inline]
+    fn rfc2822(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        cursor.space();
+        let mut named = cursor;
+        let weekday = named.weekday(3..=3);
+        if weekday.is_some() {
+            named.punct(b',')?;
+            named.space();
+            cursor = named;
+        }
+        let day = cursor.number(1..=2)?;
+        cursor.space()?;
+        let month = cursor.month(3..=3)?;
+        cursor.space()?;
+        let year = cursor.digits(2..=6)?;
+        let year = match (year.len(), lexer::value(year)) {
+            (2, yy @ 0..=49) => yy + 2000,
+            (2, yy) | (3, yy) => yy + 1900,
+            (_, year) => year,
+        };
+        let clock = cursor.clock(Layout {
+            padded: true,
+            fraction: false,
+            meridiem: false,
+            ..HMS
+        })?;
+        cursor.space()?;
+        let offset = match cursor.word() {
+            Some(name) => 3600 * Self::rfc2822_zone(name)?,
+            None => {
+                let sign = Self::sign(&mut cursor)?;
+                let zone = cursor.digits(4..=4)?;
+                let (hours, minutes) = (lexer::value(&zone[..2]), lexer::value(&zone[2..]));
+                if minutes > 59 {
+                    return None;
+                }
+                sign * (hours * 3600 + minutes * 60) as i32
+            }
+        };
+        loop {
+            let mut comment = cursor;
+            comment.space();
+            if comment.comment().is_none() {
+                break;
+            }
+            cursor = comment;
         }
+        cursor.end()?;
 
-        let now = Utc::now()
-            .date()
-            .and_time(self.default_time)?
-            .with_timezone(self.tz);
-        NaiveDate::parse_from_str(input, "%m/%d/%y")
-            .or_else(|_| NaiveDate::parse_from_str(input, "%m/%d/%Y"))
-            .ok()
-            .map(|parsed| parsed.and_time(now.time()))
-            .and_then(|datetime| self.tz.from_local_datetime(&datetime).single())
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
+        let date = NaiveDate::from_ymd_opt(year as i32, month, day)
+            .filter(|date| weekday.map_or(true, |weekday| date.weekday() == weekday))?;
+        Self::localize(
+            &FixedOffset::east_opt(offset)?,
+            date.and_time(clock.time),
+            clock.step,
+        )
+        .map(Ok)
     }
 
-    // dd/mm/yyyy
-    // - 31/3/2014
-    // - 31/03/2014
-    // - 21/08/71
-    // - 1/8/71
+    // The offset in hours of the zone names RFC 2822 has. A military zone is read as -0000.
     #[inline]
-    fn slash_dmy(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[0-9]{1,2}/[0-9]{1,2}/[0-9]{2,4}$"
-        };
-        if !re.is_match(input) {
-            return None;
+    fn rfc2822_zone(name: &[u8]) -> Option<i32> {
+        let zones: [(&[u8], i32); 11] = [
+            (b"gmt", 0),
+            (b"ut", 0),
+            (b"z", 0),
+            (b"edt", -4),
+            (b"est", -5),
+            (b"cdt", -5),
+            (b"cst", -6),
+            (b"mdt", -6),
+            (b"mst", -7),
+            (b"pdt", -7),
+            (b"pst", -8),
+        ];
+        match name {
+            [b'a'..=b'i' | b'k'..=b'y' | b'A'..=b'I' | b'K'..=b'Y'] => Some(0),
+            _ => zones
+                .iter()
+                .find(|(zone, _)| name.eq_ignore_ascii_case(zone))
+                .map(|&(_, hours)| hours),
         }
+    }
 
-        let now = Utc::now()
-            .date()
-            .and_time(self.default_time)?
-            .with_timezone(self.tz);
-        NaiveDate::parse_from_str(input, "%d/%m/%y")
-            .or_else(|_| NaiveDate::parse_from_str(input, "%d/%m/%Y"))
-            .ok()
-            .map(|parsed| parsed.and_time(now.time()))
-            .and_then(|datetime| self.tz.from_local_datetime(&datetime).single())
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
+    // yyyy
+    // - 2021
+    #[inline]
+    fn year(&self, input: &Lexed<'_>) -> Option<(NaiveDate, Precision)> {
+        let mut cursor = input.cursor();
+        let year = cursor.number(4..=4)?;
+        cursor.end()?;
+        NaiveDate::from_ymd_opt(year as i32, 1, 1).map(|date| (date, Precision::Year))
     }
 
-    // yyyy/mm/dd hh:mm:ss
-    // - 2014/4/8 22:05
-    // - 2014/04/08 22:05
-    // - 2014/04/2 03:00:51
-    // - 2014/4/02 03:00:51
-    // - 2012/03/19 10:11:59
-    // - 2012/03/19 10:11:59.3186369
+    // yyyy-mm
+    // - 2021-05
+    #[inline]
+    fn year_month(&self, input: &Lexed<'_>) -> Option<(NaiveDate, Precision)> {
+        let mut cursor = input.cursor();
+        let year = cursor.number(4..=4)?;
+        cursor.punct(b'-')?;
+        let month = cursor.number(2..=2)?;
+        cursor.end()?;
+        NaiveDate::from_ymd_opt(year as i32, month, 1).map(|date| (date, Precision::Month))
+    }
+
+    // Mon yyyy, mm/yyyy
+    // - May 2021
+    // - Sept. 2021
+    // - 05/2021
+    // - 5/2021
     #[inline]
-    fn slash_ymd_hms(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {
-                r"^[0-9]{4}/[0-9]{1,2}/[0-9]{1,2}\s+[0-9]{1,2}:[0-9]{2}(:[0-9]{2})?(\.[0-9]{1,9})?\s*(am|pm|AM|PM)?$"
+    fn month_year(&self, input: &Lexed<'_>) -> Option<(NaiveDate, Precision)> {
+        let mut cursor = input.cursor();
+        let month = match cursor.month(3..=9) {
+            Some(month) => {
+                cursor.punct(b'.');
+                cursor.space()?;
+                month
+            }
+            None => {
+                let month = cursor.number(1..=2)?;
+                cursor.punct(b'/')?;
+                month
+            }
         };
-        if !re.is_match(input) {
-            return None;
-        }
+        let year = cursor.number(4..=4)?;
+        cursor.end()?;
+        NaiveDate::from_ymd_opt(year as i32, month, 1).map(|date| (date, Precision::Month))
+    }
 
-        self.tz
-            .datetime_from_str(input, "%Y/%m/%d %H:%M:%S")
-            .or_else(|_| self.tz.datetime_from_str(input, "%Y/%m/%d %H:%M"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%Y/%m/%d %H:%M:%S%.f"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%Y/%m/%d %I:%M:%S %P"))
-            .or_else(|_| self.tz.datetime_from_str(input, "%Y/%m/%d %I:%M %P"))
-            .ok()
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
+    // Mon dd
+    // - May 14
+    // - oct. 7
+    // - Sept. 14
+    //
+    // The year is taken from the reference clock.
+    #[inline]
+    fn month_day(&self, input: &Lexed<'_>) -> Option<(NaiveDate, Precision)> {
+        let mut cursor = input.cursor();
+        let month = cursor.month(3..=9)?;
+        cursor.punct(b'.');
+        cursor.space()?;
+        let day = cursor.number(1..=2)?;
+        cursor.end()?;
+        let year = self.now().with_timezone(self.tz).year();
+        NaiveDate::from_ymd_opt(year, month, day).map(|date| (date, Precision::Day))
     }
 
-    // yyyy/mm/dd
-    // - 2014/3/31
-    // - 2014/03/31
+    // quarters, half years and fiscal years
+    // - 2021Q2
+    // - 2021-Q2
+    // - Q2 2021
+    // - H1 2021
+    // - FY2022
+    // - FY2022 Q1
+    // - Q1 FY2022
+    //
+    // Only periods marked with "FY" follow the fiscal calendar; the others are calendar periods.
     #[inline]
-    fn slash_ymd(&self, input: &str) -> Option<Result<DateTime<Utc>>> {
-        let re: &Regex = regex! {r"^[0-9]{4}/[0-9]{1,2}/[0-9]{1,2}$"
+    fn quarter(&self, input: &Lexed<'_>) -> Option<(NaiveDate, Precision)> {
+        let mut cursor = input.cursor();
+        let (fiscal, year, period) = match Self::period_marker(&mut cursor) {
+            Some((period, Some(year))) => (false, year, Some(period)),
+            Some((period, None)) => {
+                Self::gap(&mut cursor);
+                let fiscal = Self::fiscal(&mut cursor);
+                (fiscal, cursor.number(4..=4)?, Some(period))
+            }
+            None => {
+                let fiscal = Self::fiscal(&mut cursor);
+                let year = cursor.number(4..=4)?;
+                let mut marked = cursor;
+                Self::gap(&mut marked);
+                let period = match Self::period_marker(&mut marked) {
+                    Some((period, None)) => {
+                        cursor = marked;
+                        Some(period)
+                    }
+                    _ => None,
+                };
+                (fiscal, year, period)
+            }
         };
-        if !re.is_match(input) {
+        cursor.end()?;
+        if !fiscal && period.is_none() {
             return None;
         }
 
-        let now = Utc::now()
-            .date()
-            .and_time(self.default_time)?
-            .with_timezone(self.tz);
-        NaiveDate::parse_from_str(input, "%Y/%m/%d")
-            .ok()
-            .map(|parsed| parsed.and_time(now.time()))
-            .and_then(|datetime| self.tz.from_local_datetime(&datetime).single())
-            .map(|at_tz| at_tz.with_timezone(&Utc))
-            .map(Ok)
-    }
-}
+        let year = year as i32;
+        let year_start = if fiscal && self.fiscal_year_start != 1 {
+            NaiveDate::from_ymd_opt(year - 1, self.fiscal_year_start, 1)?
+        } else {
+            NaiveDate::from_ymd_opt(year, 1, 1)?
+        };
+        let Some((quarter, n)) = period else {
+            return Some((year_start, Precision::Year));
+        };
+
+        let (months, precision) = match (quarter, n) {
+            (true, _) => (3, Precision::Quarter),
+            (false, 1..=2) => (6, Precision::HalfYear),
+            _ => return None,
+        };
+        year_start
+            .checked_add_months(chrono::Months::new(months * (n - 1)))
+            .map(|start| (start, precision))
+    }
+
+    // Q1-Q4 or H1-H2, as whether it is a quarter and its number, with the year when it is
+    // written right after it, as in Q22021
+    #[inline]
+    fn period_marker(cursor: &mut Cursor<'_, '_>) -> Option<((bool, u32), Option<u32>)> {
+        let quarter = match cursor.keyword("q") {
+            Some(()) => true,
+            None => cursor.keyword("h").map(|()| false)?,
+        };
+        let mut joined = *cursor;
+        let (n, year) = match joined.number(5..=5) {
+            Some(number) => {
+                *cursor = joined;
+                (number / 10_000, Some(number % 10_000))
+            }
+            None => (cursor.number(1..=1)?, None),
+        };
+        (1..=4).contains(&n).then_some(((quarter, n), year))
+    }
+
+    // FY, followed by a gap
+    #[inline]
+    fn fiscal(cursor: &mut Cursor<'_, '_>) -> bool {
+        let fiscal = cursor.keyword("fy").is_some();
+        if fiscal {
+            Self::gap(cursor);
+        }
+        fiscal
+    }
+
+    // whitespace and a dash, each of them optional
+    #[inline]
+    fn gap(cursor: &mut Cursor<'_, '_>) {
+        cursor.space();
+        cursor.punct(b'-');
+        cursor.space();
+    }
+
+    // rfc850
+    // - Sunday, 06-Nov-94 08:49:37 GMT
+    // - Tuesday, 14-May-21 18:51:00 PDT
+    //
+    // Two-digit years follow RFC 9110: a year that appears to be more than 50 years in the
+    // future is interpreted as the most recent past year with the same last two digits.
+    #[inline]
+    fn rfc850(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let weekday = cursor.weekday(6..=9)?;
+        cursor.punct(b',')?;
+        cursor.space()?;
+        let day = cursor.number(2..=2)?;
+        cursor.punct(b'-')?;
+        let month = cursor.month(3..=3)?;
+        cursor.punct(b'-')?;
+        let yy = cursor.number(2..=2)?;
+        let clock = cursor.clock(LOG_HMS)?;
+        cursor.space()?;
+        let zone = cursor.zone(2..=5).filter(|zone| match zone {
+            [b'+' | b'-', digits @ ..] => {
+                digits.len() == 4 && digits.iter().all(u8::is_ascii_digit)
+            }
+            _ => zone.iter().all(u8::is_ascii_alphabetic),
+        })?;
+        cursor.end()?;
+
+        let offset = match Self::offset(zone) {
+            Ok(offset) => offset,
+            Err(err) => return Some(Err(err)),
+        };
+        let year = TwoDigitYear::Sliding(50).expand(yy as i32, self.now().year())?;
+        let date =
+            NaiveDate::from_ymd_opt(year, month, day).filter(|date| date.weekday() == weekday)?;
+        Self::localize(&offset, date.and_time(clock.time), clock.step).map(Ok)
+    }
+
+    // yyyy-mm-dd hh:mm:ss
+    // - 2014-04-26 05:24:37 PM
+    // - 2021-04-30 21:14
+    // - 2021-04-30 21:14:10
+    // - 2021-04-30 21:14:10.052282
+    // - 2014-04-26 17:24:37.123
+    // - 2014-04-26 17:24:37.3186369
+    // - 2012-08-03 18:31:59.257000000
+    #[inline]
+    fn ymd_hms(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = Self::iso_date(&mut cursor)?;
+        let clock = cursor.clock(Layout {
+            padded: true,
+            ..HMS
+        })?;
+        cursor.end()?;
+        Self::localize(self.tz, date.and_time(clock.time), clock.step).map(Ok)
+    }
+
+    // yyyy-mm-dd hh:mm:ss z
+    // - 2017-11-25 13:31:15 PST
+    // - 2017-11-25 13:31 PST
+    // - 2014-12-16 06:20:00 UTC
+    // - 2014-12-16 06:20:00 GMT
+    // - 2014-04-26 13:13:43 +0800
+    // - 2014-04-26 13:13:44 +09:00
+    // - 2012-08-03 18:31:59.257000000 +0000
+    // - 2015-09-30 18:48:56.35272715 UTC
+    #[inline]
+    fn ymd_hms_z(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = Self::iso_date(&mut cursor)?;
+        let clock = cursor.clock(Layout {
+            padded: true,
+            meridiem: false,
+            ..HMS
+        })?;
+        cursor.space();
+        let zone = cursor.zone(3..=6)?;
+        cursor.end()?;
+
+        match Self::offset(zone) {
+            Ok(offset) => Self::localize(&offset, date.and_time(clock.time), clock.step).map(Ok),
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    // yyyy-mm-dd
+    // - 2021-02-21
+    #[inline]
+    fn ymd(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = Self::iso_date(&mut cursor)?;
+        cursor.end()?;
+        self.at_default_time(self.tz, date).map(Ok)
+    }
+
+    // yyyy-mm-dd z
+    // - 2021-02-21 PST
+    // - 2021-02-21 UTC
+    // - 2020-07-20+08:00 (yyyy-mm-dd-07:00)
+    #[inline]
+    fn ymd_z(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = Self::iso_date(&mut cursor)?;
+        cursor.space();
+        let zone = cursor.zone(3..=6)?;
+        cursor.end()?;
+
+        match Self::offset(zone) {
+            Ok(offset) => self.at_default_time(&offset, date).map(Ok),
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    // yyyy-mon-dd
+    // - 2021-Feb-21
+    #[inline]
+    fn month_ymd(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let year = cursor.number(4..=4)?;
+        cursor.punct(b'-')?;
+        let month = cursor.month(3..=9)?;
+        cursor.punct(b'-')?;
+        let day = cursor.number(2..=2)?;
+        cursor.end()?;
+        let date = NaiveDate::from_ymd_opt(year as i32, month, day)?;
+        self.at_default_time(self.tz, date).map(Ok)
+    }
+
+    // Mon dd, yyyy, hh:mm:ss
+    // - May 8, 2009 5:57:51 PM
+    // - September 17, 2012 10:09am
+    // - September 17, 2012, 10:10:09
+    #[inline]
+    fn month_mdy_hms(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = self.month_mdy_date(&mut cursor)?;
+        let clock = cursor.clock(Layout {
+            separator: Separator::Comma,
+            fraction: false,
+            ..HMS
+        })?;
+        cursor.end()?;
+        Self::localize(self.tz, date.and_time(clock.time), clock.step).map(Ok)
+    }
+
+    // Mon dd, yyyy hh:mm:ss z
+    // - May 02, 2021 15:51:31 UTC
+    // - May 02, 2021 15:51 UTC
+    // - May 26, 2021, 12:49 AM PDT
+    // - September 17, 2012 at 10:09am PST
+    #[inline]
+    fn month_mdy_hms_z(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let month = cursor.month(3..=9)?;
+        cursor.space()?;
+        let day = cursor.number(1..=2)?;
+        cursor.punct(b',');
+        cursor.space()?;
+        let year = cursor.number(4..=4)?;
+        let clock = cursor.clock(Layout {
+            separator: Separator::CommaAt,
+            padded: true,
+            fraction: false,
+            ..HMS
+        })?;
+        cursor.space()?;
+        let zone = cursor.zone(3..=6)?;
+        cursor.end()?;
+
+        match Self::offset(zone) {
+            Ok(offset) => NaiveDate::from_ymd_opt(year as i32, month, day)
+                .and_then(|date| Self::localize(&offset, date.and_time(clock.time), clock.step))
+                .map(Ok),
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    // Mon dd, yyyy
+    // - May 25, 2021
+    // - oct 7, 1970
+    // - oct 7, 70
+    // - oct. 7, 1970
+    // - oct. 7, 70
+    // - October 7, 1970
+    #[inline]
+    fn month_mdy(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = self.month_mdy_date(&mut cursor)?;
+        cursor.end()?;
+        self.at_default_time(self.tz, date).map(Ok)
+    }
+
+    // dd Mon yyyy hh:mm:ss
+    // - 12 Feb 2006, 19:17
+    // - 12 Feb 2006 19:17
+    // - 14 May 2019 19:11:40.164
+    #[inline]
+    fn month_dmy_hms(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = self.month_dmy_date(&mut cursor)?;
+        let clock = cursor.clock(Layout {
+            separator: Separator::Comma,
+            ..HMS
+        })?;
+        cursor.end()?;
+        Self::localize(self.tz, date.and_time(clock.time), clock.step).map(Ok)
+    }
+
+    // dd Mon yyyy
+    // - 7 oct 70
+    // - 7 oct 1970
+    // - 03 February 2013
+    // - 1 July 2013
+    #[inline]
+    fn month_dmy(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = self.month_dmy_date(&mut cursor)?;
+        cursor.end()?;
+        self.at_default_time(self.tz, date).map(Ok)
+    }
+
+    // mm/dd/yyyy hh:mm:ss
+    // - 4/8/2014 22:05
+    // - 04/08/2014 22:05
+    // - 4/8/14 22:05
+    // - 04/2/2014 03:00:51
+    // - 8/8/1965 12:00:00 AM
+    // - 8/8/1965 01:00:01 PM
+    // - 8/8/1965 01:00 PM
+    // - 8/8/1965 1:00 PM
+    // - 8/8/1965 12:00 AM
+    // - 4/02/2014 03:00:51
+    // - 03/19/2012 10:11:59
+    // - 03/19/2012 10:11:59.3186369
+    #[inline]
+    fn slash_mdy_hms(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = self.slash_date(&mut cursor, false)?;
+        let clock = cursor.clock(HMS)?;
+        cursor.end()?;
+        Self::localize(self.tz, date.and_time(clock.time), clock.step).map(Ok)
+    }
+
+    // dd/mm/yyyy hh:mm:ss
+    // - 8/4/2014 22:05
+    // - 08/04/2014 22:05
+    // - 8/4/14 22:05
+    // - 2/04/2014 03:00:51
+    // - 8/8/1965 12:00:00 AM
+    // - 8/8/1965 01:00:01 PM
+    // - 8/8/1965 01:00 PM
+    // - 8/8/1965 1:00 PM
+    // - 8/8/1965 12:00 AM
+    // - 02/4/2014 03:00:51
+    // - 19/03/2012 10:11:59
+    // - 19/03/2012 10:11:59.3186369
+    #[inline]
+    fn slash_dmy_hms(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = self.slash_date(&mut cursor, true)?;
+        let clock = cursor.clock(HMS)?;
+        cursor.end()?;
+        Self::localize(self.tz, date.and_time(clock.time), clock.step).map(Ok)
+    }
+
+    // mm/dd/yyyy
+    // - 3/31/2014
+    // - 03/31/2014
+    // - 08/21/71
+    // - 8/1/71
+    #[inline]
+    fn slash_mdy(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = self.slash_date(&mut cursor, false)?;
+        cursor.end()?;
+        self.at_default_time(self.tz, date).map(Ok)
+    }
+
+    // dd/mm/yyyy
+    // - 31/3/2014
+    // - 31/03/2014
+    // - 21/08/71
+    // - 1/8/71
+    #[inline]
+    fn slash_dmy(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = self.slash_date(&mut cursor, true)?;
+        cursor.end()?;
+        self.at_default_time(self.tz, date).map(Ok)
+    }
+
+    // yyyy/mm/dd hh:mm:ss
+    // - 2014/4/8 22:05
+    // - 2014/04/08 22:05
+    // - 2014/04/2 03:00:51
+    // - 2014/4/02 03:00:51
+    // - 2012/03/19 10:11:59
+    // - 2012/03/19 10:11:59.3186369
+    #[inline]
+    fn slash_ymd_hms(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = Self::slash_ymd_date(&mut cursor)?;
+        let clock = cursor.clock(HMS)?;
+        cursor.end()?;
+        Self::localize(self.tz, date.and_time(clock.time), clock.step).map(Ok)
+    }
+
+    // yyyy/mm/dd
+    // - 2014/3/31
+    // - 2014/03/31
+    #[inline]
+    fn slash_ymd(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let date = Self::slash_ymd_date(&mut cursor)?;
+        cursor.end()?;
+        self.at_default_time(self.tz, date).map(Ok)
+    }
+
+    // Mon dd hh:mm:ss (syslog, RFC 3164)
+    // - May 27 02:45:27
+    // - Jun  2 06:31:39
+    //
+    // The year is not part of the input. It is inferred from the reference clock as the most
+    // recent year that does not put the timestamp more than a day in the future, so a "Dec 31"
+    // line read on Jan 1 belongs to the previous year.
+    #[inline]
+    fn month_md_hms(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let month = cursor.month(3..=3)?;
+        cursor.space()?;
+        let day = cursor.number(1..=2)?;
+        let clock = cursor.clock(LOG_HMS)?;
+        cursor.end()?;
+
+        let now = self.now();
+        let latest = now + chrono::Duration::days(1);
+        let this_year = now.with_timezone(self.tz).year();
+        [this_year + 1, this_year, this_year - 1]
+            .into_iter()
+            .filter_map(|year| {
+                let date = NaiveDate::from_ymd_opt(year, month, day)?;
+                Self::localize(self.tz, date.and_time(clock.time), clock.step)
+            })
+            .find(|datetime| *datetime <= latest)
+            .map(Ok)
+    }
+
+    // [dd/Mon/yyyy:hh:mm:ss z] (Apache/Nginx Common Log Format)
+    // - [14/May/2021:18:51:00 -0700]
+    // - 14/May/2021:18:51:00 +0000
+    #[inline]
+    fn clf(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let bracket = cursor.punct(b'[').is_some();
+        let day = cursor.number(1..=2)?;
+        cursor.punct(b'/')?;
+        let month = cursor.month(3..=3)?;
+        cursor.punct(b'/')?;
+        let year = cursor.number(4..=4)?;
+        let clock = cursor.clock(Layout {
+            separator: Separator::Colon,
+            ..LOG_HMS
+        })?;
+        cursor.space()?;
+        let zone = cursor.zone(5..=5).filter(|zone| {
+            matches!(zone, [b'+' | b'-', digits @ ..] if digits.iter().all(u8::is_ascii_digit))
+        })?;
+        // brackets must be balanced
+        if bracket {
+            cursor.punct(b']')?;
+        }
+        cursor.end()?;
+
+        let offset = Self::offset(zone).ok()?;
+        let date = NaiveDate::from_ymd_opt(year as i32, month, day)?;
+        Self::localize(&offset, date.and_time(clock.time), clock.step).map(Ok)
+    }
+
+    // Day Mon dd hh:mm:ss yyyy (asctime, ctime)
+    // - Wed Jun  2 06:31:39 2021
+    // - Sun Nov 6 08:49:37 1994
+    //
+    // asctime carries no zone. HTTP defines it as GMT, but like the other zone-less formats it
+    // is interpreted in the parser's timezone.
+    #[inline]
+    fn asctime(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let (weekday, month, day) = Self::asctime_date(&mut cursor)?;
+        let clock = cursor.clock(LOG_HMS)?;
+        cursor.space()?;
+        let year = cursor.number(4..=4)?;
+        cursor.end()?;
+
+        let date = NaiveDate::from_ymd_opt(year as i32, month, day)
+            .filter(|date| date.weekday() == weekday)?;
+        Self::localize(self.tz, date.and_time(clock.time), clock.step).map(Ok)
+    }
+
+    // Day Mon dd hh:mm:ss z yyyy (Unix `date`)
+    // - Wed Jun  2 06:31:39 PDT 2021
+    // - Wed Jun 2 06:31:39 UTC 2021
+    // - Wed Jun 2 06:31:39 +0200 2021
+    #[inline]
+    fn unix_date(&self, input: &Lexed<'_>) -> Option<Result<DateTime<FixedOffset>>> {
+        let mut cursor = input.cursor();
+        let (weekday, month, day) = Self::asctime_date(&mut cursor)?;
+        let clock = cursor.clock(LOG_HMS)?;
+        cursor.space()?;
+        let zone = cursor.zone(3..=6)?;
+        cursor.space()?;
+        let year = cursor.number(4..=4)?;
+        cursor.end()?;
+
+        match Self::offset(zone) {
+            Ok(offset) => NaiveDate::from_ymd_opt(year as i32, month, day)
+                .filter(|date| date.weekday() == weekday)
+                .and_then(|date| Self::localize(&offset, date.and_time(clock.time), clock.step))
+                .map(Ok),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use super::*;
 
+    fn lex(input: &str) -> Lexed<'_> {
+        lexer::lex(input.as_bytes())
+    }
+
+    #[test]
+    fn unix_timestamp() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = vec![
+            ("0", Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)),
+            ("0000000000", Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)),
+            ("0000000000000", Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)),
+            ("0000000000000000000", Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)),
+            ("-770172300", Utc.ymd(1945, 8, 5).and_hms(23, 15, 0)),
+            (
+                "1671673426.123456789",
+                Utc.ymd(2022, 12, 22).and_hms_nano(1, 43, 46, 123456768),
+            ),
+            ("1511648546", Utc.ymd(2017, 11, 25).and_hms(22, 22, 26)),
+            (".5", Utc.ymd(1970, 1, 1).and_hms_milli(0, 0, 0, 500)),
+            (
+                "1620036248.420",
+                Utc.ymd(2021, 5, 3).and_hms_milli(10, 4, 8, 420),
+            ),
+            (
+                "1620036248.717915136",
+                Utc.ymd(2021, 5, 3).and_hms_nano(10, 4, 8, 717915136),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.unix_timestamp(&lex(input)).unwrap().unwrap(),
+                want,
+                "unix_timestamp/{}",
+                input
+            )
+        }
+        assert!(parse.unix_timestamp(&lex("15116")).is_some());
+        assert!(parse
+            .unix_timestamp(&lex("16200248727179150001620024872717915000")) //DevSkim: ignore DS173237
+            .is_some());
+        assert!(parse.unix_timestamp(&lex("not-a-ts")).is_none());
+    }
+
+    #[test]
+    fn rfc3339() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            (
+                "2021-05-01T01:17:02.604456Z",
+                Utc.ymd(2021, 5, 1).and_hms_nano(1, 17, 2, 604456000),
+            ),
+            (
+                "2017-11-25T22:34:50Z",
+                Utc.ymd(2017, 11, 25).and_hms(22, 34, 50),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.rfc3339(&lex(input)).unwrap().unwrap(),
+                want,
+                "rfc3339/{}",
+                input
+            )
+        }
+        assert!(parse.rfc3339(&lex("2017-11-25 22:34:50")).is_none());
+        assert!(parse.rfc3339(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn rfc2822() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            (
+                "Wed, 02 Jun 2021 06:31:39 GMT",
+                Utc.ymd(2021, 6, 2).and_hms(6, 31, 39),
+            ),
+            (
+                "Wed, 02 Jun 2021 06:31:39 PDT",
+                Utc.ymd(2021, 6, 2).and_hms(13, 31, 39),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.rfc2822(&lex(input)).unwrap().unwrap(),
+                want,
+                "rfc2822/{}",
+                input
+            )
+        }
+        assert!(parse.rfc2822(&lex("02 Jun 2021 06:31:39")).is_none());
+        assert!(parse.rfc2822(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn rfc850() {
+        let mut parse = Parse::new(&Utc, Utc::now().time());
+        parse.reference_time(Utc.ymd(2021, 6, 15).and_hms(12, 0, 0));
+
+        let test_cases = [
+            (
+                "Sunday, 06-Nov-94 08:49:37 GMT",
+                Utc.ymd(1994, 11, 6).and_hms(8, 49, 37),
+            ),
+            (
+                "Friday, 14-May-21 18:51:00 PDT",
+                Utc.ymd(2021, 5, 15).and_hms(1, 51, 0),
+            ),
+            (
+                "Wednesday, 01-Jan-70 00:00:00 GMT",
+                Utc.ymd(2070, 1, 1).and_hms(0, 0, 0),
+            ),
+            (
+                "Thursday, 01-Jan-71 00:00:00 GMT",
+                Utc.ymd(2071, 1, 1).and_hms(0, 0, 0),
+            ),
+            (
+                "Saturday, 01-Jan-72 00:00:00 GMT",
+                Utc.ymd(1972, 1, 1).and_hms(0, 0, 0),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.rfc850(&lex(input)).unwrap().unwrap(),
+                want,
+                "rfc850/{}",
+                input
+            )
+        }
+        assert!(parse.rfc850(&lex("Sun, 06-Nov-94 08:49:37 GMT")).is_none());
+        assert!(parse.rfc850(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn http_date_family() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ];
+
+        for &input in test_cases.iter() {
+            assert_eq!(
+                parse.http_date_family(&lex(input)).unwrap().unwrap(),
+                Utc.ymd(1994, 11, 6).and_hms(8, 49, 37),
+                "http_date_family/{}",
+                input
+            )
+        }
+        assert!(parse.http_date_family(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn ymd_hms() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = vec![
+            ("2021-04-30 21:14", Utc.ymd(2021, 4, 30).and_hms(21, 14, 0)),
+            (
+                "2021-04-30 21:14:10",
+                Utc.ymd(2021, 4, 30).and_hms(21, 14, 10),
+            ),
+            (
+                "2021-04-30 21:14:10.052282",
+                Utc.ymd(2021, 4, 30).and_hms_micro(21, 14, 10, 52282),
+            ),
+            (
+                "2014-04-26 05:24:37 PM",
+                Utc.ymd(2014, 4, 26).and_hms(17, 24, 37),
+            ),
+            (
+                "2014-04-26 17:24:37.123",
+                Utc.ymd(2014, 4, 26).and_hms_milli(17, 24, 37, 123),
+            ),
+            (
+                "2014-04-26 17:24:37.3186369",
+                Utc.ymd(2014, 4, 26).and_hms_nano(17, 24, 37, 318636900),
+            ),
+            (
+                "2012-08-03 18:31:59.257000000",
+                Utc.ymd(2012, 8, 3).and_hms_nano(18, 31, 59, 257000000),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.ymd_hms(&lex(input)).unwrap().unwrap(),
+                want,
+                "ymd_hms/{}",
+                input
+            )
+        }
+        assert!(parse.ymd_hms(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn ymd_hms_z() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = vec![
+            (
+                "2017-11-25 13:31:15 PST",
+                Utc.ymd(2017, 11, 25).and_hms(21, 31, 15),
+            ),
+            (
+                "2017-11-25 13:31 PST",
+                Utc.ymd(2017, 11, 25).and_hms(21, 31, 0),
+            ),
+            (
+                "2014-12-16 06:20:00 UTC",
+                Utc.ymd(2014, 12, 16).and_hms(6, 20, 0),
+            ),
+            (
+                "2014-12-16 06:20:00 GMT",
+                Utc.ymd(2014, 12, 16).and_hms(6, 20, 0),
+            ),
+            (
+                "2014-04-26 13:13:43 +0800",
+                Utc.ymd(2014, 4, 26).and_hms(5, 13, 43),
+            ),
+            (
+                "2014-04-26 13:13:44 +09:00",
+                Utc.ymd(2014, 4, 26).and_hms(4, 13, 44),
+            ),
+            (
+                "2012-08-03 18:31:59.257000000 +0000",
+                Utc.ymd(2012, 8, 3).and_hms_nano(18, 31, 59, 257000000),
+            ),
+            (
+                "2015-09-30 18:48:56.35272715 UTC",
+                Utc.ymd(2015, 9, 30).and_hms_nano(18, 48, 56, 352727150),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.ymd_hms_z(&lex(input)).unwrap().unwrap(),
+                want,
+                "ymd_hms_z/{}",
+                input
+            )
+        }
+        assert!(parse.ymd_hms_z(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn ymd() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [(
+            "2021-02-21",
+            Utc.ymd(2021, 2, 21).and_time(Utc::now().time()),
+        )];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse
+                    .ymd(&lex(input))
+                    .unwrap()
+                    .unwrap()
+                    .trunc_subsecs(0)
+                    .with_second(0)
+                    .unwrap(),
+                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
+                "ymd/{}",
+                input
+            )
+        }
+        assert!(parse.ymd(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn ymd_z() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+        let now_at_pst = Utc::now().with_timezone(&FixedOffset::west(8 * 3600));
+        let now_at_cst = Utc::now().with_timezone(&FixedOffset::east(8 * 3600));
+
+        let test_cases = [
+            (
+                "2021-02-21 PST",
+                FixedOffset::west(8 * 3600)
+                    .ymd(2021, 2, 21)
+                    .and_time(now_at_pst.time())
+                    .map(|dt| dt.with_timezone(&Utc)),
+            ),
+            (
+                "2021-02-21 UTC",
+                FixedOffset::west(0)
+                    .ymd(2021, 2, 21)
+                    .and_time(Utc::now().time())
+                    .map(|dt| dt.with_timezone(&Utc)),
+            ),
+            (
+                "2020-07-20+08:00",
+                FixedOffset::east(8 * 3600)
+                    .ymd(2020, 7, 20)
+                    .and_time(now_at_cst.time())
+                    .map(|dt| dt.with_timezone(&Utc)),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse
+                    .ymd_z(&lex(input))
+                    .unwrap()
+                    .unwrap()
+                    .trunc_subsecs(0)
+                    .with_second(0)
+                    .unwrap(),
+                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
+                "ymd_z/{}",
+                input
+            )
+        }
+        assert!(parse.ymd_z(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn month_ymd() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [(
+            "2021-Feb-21",
+            Utc.ymd(2021, 2, 21).and_time(Utc::now().time()),
+        )];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse
+                    .month_ymd(&lex(input))
+                    .unwrap()
+                    .unwrap()
+                    .trunc_subsecs(0)
+                    .with_second(0)
+                    .unwrap(),
+                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
+                "month_ymd/{}",
+                input
+            )
+        }
+        assert!(parse.month_ymd(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn month_mdy_hms() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            (
+                "May 8, 2009 5:57:51 PM",
+                Utc.ymd(2009, 5, 8).and_hms(17, 57, 51),
+            ),
+            (
+                "September 17, 2012 10:09am",
+                Utc.ymd(2012, 9, 17).and_hms(10, 9, 0),
+            ),
+            (
+                "September 17, 2012, 10:10:09",
+                Utc.ymd(2012, 9, 17).and_hms(10, 10, 9),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.month_mdy_hms(&lex(input)).unwrap().unwrap(),
+                want,
+                "month_mdy_hms/{}",
+                input
+            )
+        }
+        assert!(parse.month_mdy_hms(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn month_mdy_hms_z() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            (
+                "May 02, 2021 15:51:31 UTC",
+                Utc.ymd(2021, 5, 2).and_hms(15, 51, 31),
+            ),
+            (
+                "May 02, 2021 15:51 UTC",
+                Utc.ymd(2021, 5, 2).and_hms(15, 51, 0),
+            ),
+            (
+                "May 26, 2021, 12:49 AM PDT",
+                Utc.ymd(2021, 5, 26).and_hms(7, 49, 0),
+            ),
+            (
+                "September 17, 2012 at 10:09am PST",
+                Utc.ymd(2012, 9, 17).and_hms(18, 9, 0),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.month_mdy_hms_z(&lex(input)).unwrap().unwrap(),
+                want,
+                "month_mdy_hms_z/{}",
+                input
+            )
+        }
+        assert!(parse.month_mdy_hms_z(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn month_mdy() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            (
+                "May 25, 2021",
+                Utc.ymd(2021, 5, 25).and_time(Utc::now().time()),
+            ),
+            (
+                "oct 7, 1970",
+                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+            ),
+            (
+                "oct 7, 70",
+                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+            ),
+            (
+                "oct. 7, 1970",
+                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+            ),
+            (
+                "oct. 7, 70",
+                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+            ),
+            (
+                "October 7, 1970",
+                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse
+                    .month_mdy(&lex(input))
+                    .unwrap()
+                    .unwrap()
+                    .trunc_subsecs(0)
+                    .with_second(0)
+                    .unwrap(),
+                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
+                "month_mdy/{}",
+                input
+            )
+        }
+        assert!(parse.month_mdy(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn month_dmy_hms() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            (
+                "12 Feb 2006, 19:17",
+                Utc.ymd(2006, 2, 12).and_hms(19, 17, 0),
+            ),
+            ("12 Feb 2006 19:17", Utc.ymd(2006, 2, 12).and_hms(19, 17, 0)),
+            (
+                "14 May 2019 19:11:40.164",
+                Utc.ymd(2019, 5, 14).and_hms_milli(19, 11, 40, 164),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.month_dmy_hms(&lex(input)).unwrap().unwrap(),
+                want,
+                "month_dmy_hms/{}",
+                input
+            )
+        }
+        assert!(parse.month_dmy_hms(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn month_dmy() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            ("7 oct 70", Utc.ymd(1970, 10, 7).and_time(Utc::now().time())),
+            (
+                "7 oct 1970",
+                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+            ),
+            (
+                "03 February 2013",
+                Utc.ymd(2013, 2, 3).and_time(Utc::now().time()),
+            ),
+            (
+                "1 July 2013",
+                Utc.ymd(2013, 7, 1).and_time(Utc::now().time()),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse
+                    .month_dmy(&lex(input))
+                    .unwrap()
+                    .unwrap()
+                    .trunc_subsecs(0)
+                    .with_second(0)
+                    .unwrap(),
+                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
+                "month_dmy/{}",
+                input
+            )
+        }
+        assert!(parse.month_dmy(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn slash_mdy_hms() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = vec![
+            ("4/8/2014 22:05", Utc.ymd(2014, 4, 8).and_hms(22, 5, 0)),
+            ("04/08/2014 22:05", Utc.ymd(2014, 4, 8).and_hms(22, 5, 0)),
+            ("4/8/14 22:05", Utc.ymd(2014, 4, 8).and_hms(22, 5, 0)),
+            ("04/2/2014 03:00:51", Utc.ymd(2014, 4, 2).and_hms(3, 0, 51)),
+            ("8/8/1965 12:00:00 AM", Utc.ymd(1965, 8, 8).and_hms(0, 0, 0)),
+            (
+                "8/8/1965 01:00:01 PM",
+                Utc.ymd(1965, 8, 8).and_hms(13, 0, 1),
+            ),
+            ("8/8/1965 01:00 PM", Utc.ymd(1965, 8, 8).and_hms(13, 0, 0)),
+            ("8/8/1965 1:00 PM", Utc.ymd(1965, 8, 8).and_hms(13, 0, 0)),
+            ("8/8/1965 12:00 AM", Utc.ymd(1965, 8, 8).and_hms(0, 0, 0)),
+            ("4/02/2014 03:00:51", Utc.ymd(2014, 4, 2).and_hms(3, 0, 51)),
+            (
+                "03/19/2012 10:11:59",
+                Utc.ymd(2012, 3, 19).and_hms(10, 11, 59),
+            ),
+            (
+                "03/19/2012 10:11:59.3186369",
+                Utc.ymd(2012, 3, 19).and_hms_nano(10, 11, 59, 318636900),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.slash_mdy_hms(&lex(input)).unwrap().unwrap(),
+                want,
+                "slash_mdy_hms/{}",
+                input
+            )
+        }
+        assert!(parse.slash_mdy_hms(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn slash_mdy() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            (
+                "3/31/2014",
+                Utc.ymd(2014, 3, 31).and_time(Utc::now().time()),
+            ),
+            (
+                "03/31/2014",
+                Utc.ymd(2014, 3, 31).and_time(Utc::now().time()),
+            ),
+            ("08/21/71", Utc.ymd(1971, 8, 21).and_time(Utc::now().time())),
+            ("8/1/71", Utc.ymd(1971, 8, 1).and_time(Utc::now().time())),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse
+                    .slash_mdy(&lex(input))
+                    .unwrap()
+                    .unwrap()
+                    .trunc_subsecs(0)
+                    .with_second(0)
+                    .unwrap(),
+                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
+                "slash_mdy/{}",
+                input
+            )
+        }
+        assert!(parse.slash_mdy(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn slash_dmy() {
+        let mut parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            (
+                "31/3/2014",
+                Utc.ymd(2014, 3, 31).and_time(Utc::now().time()),
+            ),
+            (
+                "13/11/2014",
+                Utc.ymd(2014, 11, 13).and_time(Utc::now().time()),
+            ),
+            ("21/08/71", Utc.ymd(1971, 8, 21).and_time(Utc::now().time())),
+            ("1/8/71", Utc.ymd(1971, 8, 1).and_time(Utc::now().time())),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse
+                    .prefer_dmy(true)
+                    .slash_dmy(&lex(input))
+                    .unwrap()
+                    .unwrap()
+                    .trunc_subsecs(0)
+                    .with_second(0)
+                    .unwrap(),
+                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
+                "slash_dmy/{}",
+                input
+            )
+        }
+        assert!(parse.slash_dmy(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn slash_ymd_hms() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            ("2014/4/8 22:05", Utc.ymd(2014, 4, 8).and_hms(22, 5, 0)),
+            ("2014/04/08 22:05", Utc.ymd(2014, 4, 8).and_hms(22, 5, 0)),
+            ("2014/04/2 03:00:51", Utc.ymd(2014, 4, 2).and_hms(3, 0, 51)),
+            ("2014/4/02 03:00:51", Utc.ymd(2014, 4, 2).and_hms(3, 0, 51)),
+            (
+                "2012/03/19 10:11:59",
+                Utc.ymd(2012, 3, 19).and_hms(10, 11, 59),
+            ),
+            (
+                "2012/03/19 10:11:59.3186369",
+                Utc.ymd(2012, 3, 19).and_hms_nano(10, 11, 59, 318636900),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.slash_ymd_hms(&lex(input)).unwrap().unwrap(),
+                want,
+                "slash_ymd_hms/{}",
+                input
+            )
+        }
+        assert!(parse.slash_ymd_hms(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn slash_ymd() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            (
+                "2014/3/31",
+                Utc.ymd(2014, 3, 31).and_time(Utc::now().time()),
+            ),
+            (
+                "2014/03/31",
+                Utc.ymd(2014, 3, 31).and_time(Utc::now().time()),
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse
+                    .slash_ymd(&lex(input))
+                    .unwrap()
+                    .unwrap()
+                    .trunc_subsecs(0)
+                    .with_second(0)
+                    .unwrap(),
+                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
+                "slash_ymd/{}",
+                input
+            )
+        }
+        assert!(parse.slash_ymd(&lex("not-date-time")).is_none());
+    }
+
+    #[test]
+    fn month_md_hms() {
+        let mut parse = Parse::new(&Utc, Utc::now().time());
+        parse.reference_time(Utc.ymd(2021, 6, 15).and_hms(12, 0, 0));
+
+        let test_cases = [
+            ("May 27 02:45:27", Utc.ymd(2021, 5, 27).and_hms(2, 45, 27)),
+            ("Jun  2 06:31:39", Utc.ymd(2021, 6, 2).and_hms(6, 31, 39)),
+            ("Jun 16 06:31:39", Utc.ymd(2021, 6, 16).and_hms(6, 31, 39)),
+            ("Aug 2 06:31:39", Utc.ymd(2020, 8, 2).and_hms(6, 31, 39)),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                parse.month_md_hms(&lex(input)).unwrap().unwrap(),
+                want,
+                "month_md_hms/{}",
+                input
+            )
+        }
+        assert!(parse.month_md_hms(&lex("not-date-time")).is_none());
+
+        // year rollover around Dec/Jan
+        parse.reference_time(Utc.ymd(2022, 1, 1).and_hms(0, 5, 0));
+        assert_eq!(
+            parse
+                .month_md_hms(&lex("Dec 31 23:59:59"))
+                .unwrap()
+                .unwrap(),
+            Utc.ymd(2021, 12, 31).and_hms(23, 59, 59),
+        );
+        parse.reference_time(Utc.ymd(2021, 12, 31).and_hms(23, 58, 0));
+        assert_eq!(
+            parse
+                .month_md_hms(&lex("Jan  1 00:00:01"))
+                .unwrap()
+                .unwrap(),
+            Utc.ymd(2022, 1, 1).and_hms(0, 0, 1),
+        );
+        // Feb 29 only resolves when a leap year is within reach
+        parse.reference_time(Utc.ymd(2021, 6, 15).and_hms(12, 0, 0));
+        assert_eq!(
+            parse
+                .month_md_hms(&lex("Feb 29 10:00:00"))
+                .unwrap()
+                .unwrap(),
+            Utc.ymd(2020, 2, 29).and_hms(10, 0, 0),
+        );
+        parse.reference_time(Utc.ymd(2023, 6, 15).and_hms(12, 0, 0));
+        assert!(parse.month_md_hms(&lex("Feb 29 10:00:00")).is_none());
+    }
+
     #[test]
-    fn unix_timestamp() {
+    fn asctime() {
         let parse = Parse::new(&Utc, Utc::now().time());
 
-        let test_cases = vec![
-            ("0", Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)),
-            ("0000000000", Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)),
-            ("0000000000000", Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)),
-            ("0000000000000000000", Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)),
-            ("-770172300", Utc.ymd(1945, 8, 5).and_hms(23, 15, 0)),
+        let test_cases = [
             (
-                "1671673426.123456789",
-                Utc.ymd(2022, 12, 22).and_hms_nano(1, 43, 46, 123456768),
+                "Wed Jun  2 06:31:39 2021",
+                Utc.ymd(2021, 6, 2).and_hms(6, 31, 39),
             ),
-            ("1511648546", Utc.ymd(2017, 11, 25).and_hms(22, 22, 26)),
             (
-                "1620036248.420",
-                Utc.ymd(2021, 5, 3).and_hms_milli(10, 4, 8, 420),
+                "Sun Nov 6 08:49:37 1994",
+                Utc.ymd(1994, 11, 6).and_hms(8, 49, 37),
             ),
             (
-                "1620036248.717915136",
-                Utc.ymd(2021, 5, 3).and_hms_nano(10, 4, 8, 717915136),
+                "Thu Dec 31 23:59:59 2020",
+                Utc.ymd(2020, 12, 31).and_hms(23, 59, 59),
             ),
         ];
 
         for &(input, want) in test_cases.iter() {
             assert_eq!(
-                parse.unix_timestamp(input).unwrap().unwrap(),
+                parse.asctime(&lex(input)).unwrap().unwrap(),
                 want,
-                "unix_timestamp/{}",
+                "asctime/{}",
                 input
             )
         }
-        assert!(parse.unix_timestamp("15116").is_some());
-        assert!(parse
-            .unix_timestamp("16200248727179150001620024872717915000") //DevSkim: ignore DS173237
-            .is_some());
-        assert!(parse.unix_timestamp("not-a-ts").is_none());
+        assert!(parse.asctime(&lex("not-date-time")).is_none());
     }
 
     #[test]
-    fn rfc3339() {
+    fn unix_date() {
         let parse = Parse::new(&Utc, Utc::now().time());
 
         let test_cases = [
             (
-                "2021-05-01T01:17:02.604456Z",
-                Utc.ymd(2021, 5, 1).and_hms_nano(1, 17, 2, 604456000),
+                "Wed Jun  2 06:31:39 PDT 2021",
+                Utc.ymd(2021, 6, 2).and_hms(13, 31, 39),
             ),
             (
-                "2017-11-25T22:34:50Z",
-                Utc.ymd(2017, 11, 25).and_hms(22, 34, 50),
+                "Wed Jun 2 06:31:39 UTC 2021",
+                Utc.ymd(2021, 6, 2).and_hms(6, 31, 39),
+            ),
+            (
+                "Wed Jun 2 06:31:39 +0200 2021",
+                Utc.ymd(2021, 6, 2).and_hms(4, 31, 39),
             ),
         ];
 
         for &(input, want) in test_cases.iter() {
             assert_eq!(
-                parse.rfc3339(input).unwrap().unwrap(),
+                parse.unix_date(&lex(input)).unwrap().unwrap(),
                 want,
-                "rfc3339/{}",
+                "unix_date/{}",
                 input
             )
         }
-        assert!(parse.rfc3339("2017-11-25 22:34:50").is_none());
-        assert!(parse.rfc3339("not-date-time").is_none());
+        assert!(parse.unix_date(&lex("not-date-time")).is_none());
     }
 
     #[test]
-    fn rfc2822() {
+    fn clf() {
         let parse = Parse::new(&Utc, Utc::now().time());
 
         let test_cases = [
             (
-                "Wed, 02 Jun 2021 06:31:39 GMT",
-                Utc.ymd(2021, 6, 2).and_hms(6, 31, 39),
+                "[14/May/2021:18:51:00 -0700]",
+                Utc.ymd(2021, 5, 15).and_hms(1, 51, 0),
             ),
             (
-                "Wed, 02 Jun 2021 06:31:39 PDT",
-                Utc.ymd(2021, 6, 2).and_hms(13, 31, 39),
+                "14/May/2021:18:51:00 +0000",
+                Utc.ymd(2021, 5, 14).and_hms(18, 51, 0),
+            ),
+            (
+                "[10/Oct/2000:13:55:36 +0530]",
+                Utc.ymd(2000, 10, 10).and_hms(8, 25, 36),
             ),
         ];
 
         for &(input, want) in test_cases.iter() {
             assert_eq!(
-                parse.rfc2822(input).unwrap().unwrap(),
+                parse.clf(&lex(input)).unwrap().unwrap(),
                 want,
-                "rfc2822/{}",
+                "clf/{}",
                 input
             )
         }
-        assert!(parse.rfc2822("02 Jun 2021 06:31:39").is_none());
-        assert!(parse.rfc2822("not-date-time").is_none());
+        assert!(parse.clf(&lex("[14/May/2021:18:51:00 -0700")).is_none());
+        assert!(parse.clf(&lex("14/05/2021:18:51:00 +0000")).is_none());
+        assert!(parse.clf(&lex("not-date-time")).is_none());
     }
 
     #[test]
-    fn ymd_hms() {
-        let parse = Parse::new(&Utc, Utc::now().time());
+    fn two_digit_year() {
+        let mut parse = Parse::new(&Utc, Utc::now().time());
+        parse.reference_time(Utc.ymd(2021, 6, 15).and_hms(12, 0, 0));
 
-        let test_cases = vec![
-            ("2021-04-30 21:14", Utc.ymd(2021, 4, 30).and_hms(21, 14, 0)),
-            (
-                "2021-04-30 21:14:10",
-                Utc.ymd(2021, 4, 30).and_hms(21, 14, 10),
-            ),
-            (
-                "2021-04-30 21:14:10.052282",
-                Utc.ymd(2021, 4, 30).and_hms_micro(21, 14, 10, 52282),
-            ),
-            (
-                "2014-04-26 05:24:37 PM",
-                Utc.ymd(2014, 4, 26).and_hms(17, 24, 37),
-            ),
-            (
-                "2014-04-26 17:24:37.123",
-                Utc.ymd(2014, 4, 26).and_hms_milli(17, 24, 37, 123),
-            ),
-            (
-                "2014-04-26 17:24:37.3186369",
-                Utc.ymd(2014, 4, 26).and_hms_nano(17, 24, 37, 318636900),
-            ),
+        let year_of = |parse: &Parse<Utc>, input: &str| {
+            parse
+                .slash_mdy(&lex(input))
+                .or_else(|| parse.slash_mdy_hms(&lex(input)))
+                .or_else(|| parse.month_mdy(&lex(input)))
+                .or_else(|| parse.month_mdy_hms(&lex(input)))
+                .or_else(|| parse.month_dmy(&lex(input)))
+                .or_else(|| parse.month_dmy_hms(&lex(input)))
+                .map(|parsed| parsed.unwrap().year())
+        };
+
+        let test_cases = [
+            (TwoDigitYear::Pivot(1969), "08/21/71", Some(1971)),
+            (TwoDigitYear::Pivot(1969), "06/27/68 10:00", Some(2068)),
+            (TwoDigitYear::Pivot(1950), "06/27/68", Some(1968)),
+            (TwoDigitYear::Pivot(1950), "06/27/49 10:00", Some(2049)),
+            (TwoDigitYear::Sliding(0), "oct 7, 21", Some(2021)),
+            (TwoDigitYear::Sliding(0), "oct 7, 22", Some(1922)),
             (
-                "2012-08-03 18:31:59.257000000",
-                Utc.ymd(2012, 8, 3).and_hms_nano(18, 31, 59, 257000000),
+                TwoDigitYear::Sliding(10),
+                "May 8, 31 5:57:51 PM",
+                Some(2031),
             ),
+            (TwoDigitYear::Sliding(10), "7 oct 32", Some(1932)),
+            (TwoDigitYear::Sliding(10), "12 Feb 06 19:17", Some(2006)),
+            (TwoDigitYear::Reject, "08/21/71", None),
+            (TwoDigitYear::Reject, "4/8/14 22:05", None),
+            (TwoDigitYear::Reject, "oct 7, 70", None),
+            (TwoDigitYear::Reject, "12 Feb 06, 19:17", None),
+            (TwoDigitYear::Reject, "08/21/1971", Some(1971)),
+            (TwoDigitYear::Reject, "12 Feb 2006 19:17", Some(2006)),
         ];
 
-        for &(input, want) in test_cases.iter() {
+        for &(policy, input, want) in test_cases.iter() {
+            parse.two_digit_year(policy);
             assert_eq!(
-                parse.ymd_hms(input).unwrap().unwrap(),
+                year_of(&parse, input),
                 want,
-                "ymd_hms/{}",
+                "two_digit_year/{:?}/{}",
+                policy,
                 input
             )
         }
-        assert!(parse.ymd_hms("not-date-time").is_none());
     }
 
     #[test]
-    fn ymd_hms_z() {
-        let parse = Parse::new(&Utc, Utc::now().time());
+    fn partial_family() {
+        let mut parse = Parse::new(&Utc, Utc::now().time());
+        parse.reference_time(Utc.ymd(2021, 6, 15).and_hms(12, 0, 0));
 
-        let test_cases = vec![
+        let test_cases = [
             (
-                "2017-11-25 13:31:15 PST",
-                Utc.ymd(2017, 11, 25).and_hms(21, 31, 15),
+                "2021",
+                Utc.ymd(2021, 1, 1).and_hms(0, 0, 0),
+                Precision::Year,
             ),
             (
-                "2017-11-25 13:31 PST",
-                Utc.ymd(2017, 11, 25).and_hms(21, 31, 0),
+                "2021-05",
+                Utc.ymd(2021, 5, 1).and_hms(0, 0, 0),
+                Precision::Month,
             ),
             (
-                "2014-12-16 06:20:00 UTC",
-                Utc.ymd(2014, 12, 16).and_hms(6, 20, 0),
+                "May 2021",
+                Utc.ymd(2021, 5, 1).and_hms(0, 0, 0),
+                Precision::Month,
             ),
             (
-                "2014-12-16 06:20:00 GMT",
-                Utc.ymd(2014, 12, 16).and_hms(6, 20, 0),
+                "oct. 1970",
+                Utc.ymd(1970, 10, 1).and_hms(0, 0, 0),
+                Precision::Month,
             ),
             (
-                "2014-04-26 13:13:43 +0800",
-                Utc.ymd(2014, 4, 26).and_hms(5, 13, 43),
+                "05/2021",
+                Utc.ymd(2021, 5, 1).and_hms(0, 0, 0),
+                Precision::Month,
             ),
             (
-                "2014-04-26 13:13:44 +09:00",
-                Utc.ymd(2014, 4, 26).and_hms(4, 13, 44),
+                "Sept. 2021",
+                Utc.ymd(2021, 9, 1).and_hms(0, 0, 0),
+                Precision::Month,
             ),
             (
-                "2012-08-03 18:31:59.257000000 +0000",
-                Utc.ymd(2012, 8, 3).and_hms_nano(18, 31, 59, 257000000),
+                "SEPT 2021",
+                Utc.ymd(2021, 9, 1).and_hms(0, 0, 0),
+                Precision::Month,
             ),
             (
-                "2015-09-30 18:48:56.35272715 UTC",
-                Utc.ymd(2015, 9, 30).and_hms_nano(18, 48, 56, 352727150),
+                "May 14",
+                Utc.ymd(2021, 5, 14).and_hms(0, 0, 0),
+                Precision::Day,
+            ),
+            (
+                "Sept. 14",
+                Utc.ymd(2021, 9, 14).and_hms(0, 0, 0),
+                Precision::Day,
+            ),
+        ];
+
+        for &(input, want, precision) in test_cases.iter() {
+            assert_eq!(
+                parse.partial_family(&lex(input)).unwrap().unwrap(),
+                (want.fixed_offset(), precision),
+                "partial_family/{}",
+                input
+            )
+        }
+        assert!(parse.partial_family(&lex("2021-13")).is_none());
+        assert!(parse.partial_family(&lex("not-date-time")).is_none());
+
+        parse.partial_fill(PartialFill::End);
+        let test_cases = [
+            (
+                "2021",
+                Utc.ymd(2021, 12, 31).and_hms_nano(23, 59, 59, 999_999_999),
+            ),
+            (
+                "2020-02",
+                Utc.ymd(2020, 2, 29).and_hms_nano(23, 59, 59, 999_999_999),
+            ),
+            (
+                "Dec 2021",
+                Utc.ymd(2021, 12, 31).and_hms_nano(23, 59, 59, 999_999_999),
+            ),
+            (
+                "May 14",
+                Utc.ymd(2021, 5, 14).and_hms_nano(23, 59, 59, 999_999_999),
             ),
         ];
 
         for &(input, want) in test_cases.iter() {
             assert_eq!(
-                parse.ymd_hms_z(input).unwrap().unwrap(),
+                parse.partial_family(&lex(input)).unwrap().unwrap().0,
                 want,
-                "ymd_hms_z/{}",
+                "partial_family/end/{}",
                 input
             )
         }
-        assert!(parse.ymd_hms_z("not-date-time").is_none());
+
+        parse.partial_fill(PartialFill::Reject);
+        assert!(parse.partial_family(&lex("2021")).unwrap().is_err());
+        assert!(parse.parse("May 2021").is_err());
+        assert!(parse.parse("2021-05-14").is_ok());
     }
 
     #[test]
-    fn ymd() {
+    fn parse_with_precision() {
         let parse = Parse::new(&Utc, Utc::now().time());
 
-        let test_cases = [(
-            "2021-02-21",
-            Utc.ymd(2021, 2, 21).and_time(Utc::now().time()),
-        )];
+        let test_cases = [
+            ("2021", Precision::Year),
+            ("2021-05", Precision::Month),
+            ("2021-05-14", Precision::Day),
+            ("14 May 2021", Precision::Day),
+            ("05/14/2021", Precision::Day),
+            ("2021-05-14 18:51", Precision::Time),
+            ("1620021848", Precision::Time),
+        ];
 
         for &(input, want) in test_cases.iter() {
             assert_eq!(
-                parse
-                    .ymd(input)
-                    .unwrap()
-                    .unwrap()
-                    .trunc_subsecs(0)
-                    .with_second(0)
-                    .unwrap(),
-                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
-                "ymd/{}",
+                parse.parse_with_precision(input).unwrap().1,
+                want,
+                "parse_with_precision/{}",
                 input
             )
         }
-        assert!(parse.ymd("not-date-time").is_none());
     }
 
     #[test]
-    fn ymd_z() {
-        let parse = Parse::new(&Utc, Utc::now().time());
-        let now_at_pst = Utc::now().with_timezone(&FixedOffset::west(8 * 3600));
-        let now_at_cst = Utc::now().with_timezone(&FixedOffset::east(8 * 3600));
+    fn parse_interval() {
+        let mut parse = Parse::new(&Utc, Utc::now().time());
+        parse.reference_time(Utc.ymd(2021, 6, 15).and_hms(12, 0, 0));
 
         let test_cases = [
             (
-                "2021-02-21 PST",
-                FixedOffset::west(8 * 3600)
-                    .ymd(2021, 2, 21)
-                    .and_time(now_at_pst.time())
-                    .map(|dt| dt.with_timezone(&Utc)),
+                "2021",
+                Utc.ymd(2021, 1, 1).and_hms(0, 0, 0),
+                Utc.ymd(2022, 1, 1).and_hms(0, 0, 0),
             ),
             (
-                "2021-02-21 UTC",
-                FixedOffset::west(0)
-                    .ymd(2021, 2, 21)
-                    .and_time(Utc::now().time())
-                    .map(|dt| dt.with_timezone(&Utc)),
+                "2021-05",
+                Utc.ymd(2021, 5, 1).and_hms(0, 0, 0),
+                Utc.ymd(2021, 6, 1).and_hms(0, 0, 0),
             ),
             (
-                "2020-07-20+08:00",
-                FixedOffset::east(8 * 3600)
-                    .ymd(2020, 7, 20)
-                    .and_time(now_at_cst.time())
-                    .map(|dt| dt.with_timezone(&Utc)),
+                "May 14",
+                Utc.ymd(2021, 5, 14).and_hms(0, 0, 0),
+                Utc.ymd(2021, 5, 15).and_hms(0, 0, 0),
+            ),
+            (
+                "2021-05-14",
+                Utc.ymd(2021, 5, 14).and_hms(0, 0, 0),
+                Utc.ymd(2021, 5, 15).and_hms(0, 0, 0),
+            ),
+            (
+                "2021-05-14 18:51",
+                Utc.ymd(2021, 5, 14).and_hms(18, 51, 0),
+                Utc.ymd(2021, 5, 14).and_hms(18, 51, 0),
+            ),
+            (
+                "2021-05-01 to 2021-05-31",
+                Utc.ymd(2021, 5, 1).and_hms(0, 0, 0),
+                Utc.ymd(2021, 6, 1).and_hms(0, 0, 0),
+            ),
+            (
+                "2021-05-01/2021-05-31",
+                Utc.ymd(2021, 5, 1).and_hms(0, 0, 0),
+                Utc.ymd(2021, 6, 1).and_hms(0, 0, 0),
+            ),
+            (
+                "2021-05-01T10:00:00Z/2021-05-01T12:30:00Z",
+                Utc.ymd(2021, 5, 1).and_hms(10, 0, 0),
+                Utc.ymd(2021, 5, 1).and_hms(12, 30, 0),
+            ),
+            (
+                "May 1–31, 2021",
+                Utc.ymd(2021, 5, 1).and_hms(0, 0, 0),
+                Utc.ymd(2021, 6, 1).and_hms(0, 0, 0),
+            ),
+            (
+                "May 1-31, 2021",
+                Utc.ymd(2021, 5, 1).and_hms(0, 0, 0),
+                Utc.ymd(2021, 6, 1).and_hms(0, 0, 0),
+            ),
+            (
+                "2020 - 2021",
+                Utc.ymd(2020, 1, 1).and_hms(0, 0, 0),
+                Utc.ymd(2022, 1, 1).and_hms(0, 0, 0),
+            ),
+            (
+                "2021-05-14 10:00 - 2021-05-14 12:30",
+                Utc.ymd(2021, 5, 14).and_hms(10, 0, 0),
+                Utc.ymd(2021, 5, 14).and_hms(12, 30, 0),
+            ),
+            (
+                "May 2021 to 2021-07",
+                Utc.ymd(2021, 5, 1).and_hms(0, 0, 0),
+                Utc.ymd(2021, 8, 1).and_hms(0, 0, 0),
             ),
         ];
 
-        for &(input, want) in test_cases.iter() {
+        for &(input, start, end) in test_cases.iter() {
             assert_eq!(
-                parse
-                    .ymd_z(input)
-                    .unwrap()
-                    .unwrap()
-                    .trunc_subsecs(0)
-                    .with_second(0)
-                    .unwrap(),
-                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
-                "ymd_z/{}",
+                parse.parse_interval(input).unwrap(),
+                (start, end),
+                "parse_interval/{}",
                 input
             )
         }
-        assert!(parse.ymd_z("not-date-time").is_none());
+        assert!(parse.parse_interval("2021-05-31 to 2021-05-01").is_err());
+        assert!(parse.parse_interval("May 1 to not-date-time").is_err());
+        // a spaced offset, not the end of a range
+        assert_eq!(
+            parse
+                .parse_interval("2021-05-14 18:51 - 0700")
+                .unwrap_err()
+                .to_string(),
+            "2021-05-14 18:51 - 0700 did not match any formats."
+        );
+        assert!(parse.parse_interval("2021-05-14 18:51 - 07:00").is_err());
+        assert!(parse.parse_interval("not-date-time").is_err());
     }
 
     #[test]
-    fn month_ymd() {
-        let parse = Parse::new(&Utc, Utc::now().time());
-
-        let test_cases = [(
-            "2021-Feb-21",
-            Utc.ymd(2021, 2, 21).and_time(Utc::now().time()),
-        )];
-
-        for &(input, want) in test_cases.iter() {
-            assert_eq!(
-                parse
-                    .month_ymd(input)
-                    .unwrap()
-                    .unwrap()
-                    .trunc_subsecs(0)
-                    .with_second(0)
-                    .unwrap(),
-                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
-                "month_ymd/{}",
-                input
-            )
-        }
-        assert!(parse.month_ymd("not-date-time").is_none());
+    #[should_panic(expected = "fiscal year start month 13 is not in 1..=12")]
+    fn fiscal_year_start_out_of_range() {
+        Parse::new(&Utc, NaiveTime::MIN).fiscal_year_start(13);
     }
 
     #[test]
-    fn month_mdy_hms() {
-        let parse = Parse::new(&Utc, Utc::now().time());
+    fn quarter() {
+        let mut parse = Parse::new(&Utc, Utc::now().time());
 
         let test_cases = [
             (
-                "May 8, 2009 5:57:51 PM",
-                Utc.ymd(2009, 5, 8).and_hms(17, 57, 51),
+                "2021Q2",
+                NaiveDate::from_ymd(2021, 4, 1),
+                Precision::Quarter,
             ),
             (
-                "September 17, 2012 10:09am",
-                Utc.ymd(2012, 9, 17).and_hms(10, 9, 0),
+                "2021-Q2",
+                NaiveDate::from_ymd(2021, 4, 1),
+                Precision::Quarter,
             ),
             (
-                "September 17, 2012, 10:10:09",
-                Utc.ymd(2012, 9, 17).and_hms(10, 10, 9),
+                "2021 q4",
+                NaiveDate::from_ymd(2021, 10, 1),
+                Precision::Quarter,
+            ),
+            (
+                "Q2 2021",
+                NaiveDate::from_ymd(2021, 4, 1),
+                Precision::Quarter,
+            ),
+            (
+                "Q1-2021",
+                NaiveDate::from_ymd(2021, 1, 1),
+                Precision::Quarter,
+            ),
+            (
+                "H1 2021",
+                NaiveDate::from_ymd(2021, 1, 1),
+                Precision::HalfYear,
+            ),
+            (
+                "2021H2",
+                NaiveDate::from_ymd(2021, 7, 1),
+                Precision::HalfYear,
+            ),
+            ("FY2022", NaiveDate::from_ymd(2022, 1, 1), Precision::Year),
+            (
+                "FY2022 Q1",
+                NaiveDate::from_ymd(2022, 1, 1),
+                Precision::Quarter,
             ),
         ];
 
-        for &(input, want) in test_cases.iter() {
+        for &(input, want, precision) in test_cases.iter() {
             assert_eq!(
-                parse.month_mdy_hms(input).unwrap().unwrap(),
-                want,
-                "month_mdy_hms/{}",
+                parse.quarter(&lex(input)).unwrap(),
+                (want, precision),
+                "quarter/{}",
                 input
             )
         }
-        assert!(parse.month_mdy_hms("not-date-time").is_none());
-    }
-
-    #[test]
-    fn month_mdy_hms_z() {
-        let parse = Parse::new(&Utc, Utc::now().time());
+        assert!(parse.quarter(&lex("2021")).is_none());
+        assert!(parse.quarter(&lex("H3 2021")).is_none());
+        assert!(parse.quarter(&lex("Q5 2021")).is_none());
+        assert!(parse.quarter(&lex("not-date-time")).is_none());
 
+        parse.fiscal_year_start(10);
         let test_cases = [
+            ("FY2022", NaiveDate::from_ymd(2021, 10, 1), Precision::Year),
             (
-                "May 02, 2021 15:51:31 UTC",
-                Utc.ymd(2021, 5, 2).and_hms(15, 51, 31),
+                "FY2022 Q1",
+                NaiveDate::from_ymd(2021, 10, 1),
+                Precision::Quarter,
             ),
             (
-                "May 02, 2021 15:51 UTC",
-                Utc.ymd(2021, 5, 2).and_hms(15, 51, 0),
+                "FY2022-Q3",
+                NaiveDate::from_ymd(2022, 4, 1),
+                Precision::Quarter,
             ),
             (
-                "May 26, 2021, 12:49 AM PDT",
-                Utc.ymd(2021, 5, 26).and_hms(7, 49, 0),
+                "Q4 FY2022",
+                NaiveDate::from_ymd(2022, 7, 1),
+                Precision::Quarter,
             ),
             (
-                "September 17, 2012 at 10:09am PST",
-                Utc.ymd(2012, 9, 17).and_hms(18, 9, 0),
+                "FY2022 H2",
+                NaiveDate::from_ymd(2022, 4, 1),
+                Precision::HalfYear,
+            ),
+            (
+                "Q1 2022",
+                NaiveDate::from_ymd(2022, 1, 1),
+                Precision::Quarter,
+            ),
+        ];
+
+        for &(input, want, precision) in test_cases.iter() {
+            assert_eq!(
+                parse.quarter(&lex(input)).unwrap(),
+                (want, precision),
+                "quarter/fiscal/{}",
+                input
+            )
+        }
+
+        assert_eq!(
+            parse.parse("FY2022 Q2").unwrap(),
+            Utc.ymd(2022, 1, 1).and_hms(0, 0, 0),
+        );
+        assert_eq!(
+            parse.parse_interval("FY2022").unwrap(),
+            (
+                Utc.ymd(2021, 10, 1).and_hms(0, 0, 0),
+                Utc.ymd(2022, 10, 1).and_hms(0, 0, 0),
+            ),
+        );
+        assert_eq!(
+            parse.parse_interval("Q2 2021").unwrap(),
+            (
+                Utc.ymd(2021, 4, 1).and_hms(0, 0, 0),
+                Utc.ymd(2021, 7, 1).and_hms(0, 0, 0),
             ),
+        );
+    }
+
+    #[test]
+    fn end_of_day() {
+        let tz = FixedOffset::east(2 * 3600);
+        let parse = Parse::new(&tz, Utc::now().time());
+
+        let test_cases = [
+            "2021-05-14 24:00:00",
+            "2021-05-14 24:00",
+            "2021-05-14 24:00:00.000",
+            "2021-05-14T24:00:00+02:00",
+            "2021-05-14 24:00:00 +0200",
+            "05/14/2021 24:00",
+            "2021/05/14 24:00:00",
+            "May 14, 2021 24:00",
+            "14 May 2021 24:00:00",
+            "May 14, 2021 24:00 +0200",
+            "14/May/2021:24:00:00 +0200",
+        ];
+
+        for &input in test_cases.iter() {
+            assert_eq!(
+                parse.parse(input).unwrap(),
+                Utc.ymd(2021, 5, 14).and_hms(22, 0, 0),
+                "end_of_day/{}",
+                input
+            )
+        }
+        assert_eq!(lex("2021-05-14 18:24:00").end_of_day(), 0);
+        assert!(parse.parse("2021-05-14 24:00:01").is_err());
+        assert!(parse.parse("2021-05-14 24:01").is_err());
+    }
+
+    #[test]
+    fn leap_second() {
+        let mut parse = Parse::new(&Utc, Utc::now().time());
+
+        let test_cases = [
+            "2016-12-31 23:59:60",
+            "2016-12-31T23:59:60Z",
+            "2017-01-01 00:59:60 +0100",
+            "12/31/2016 23:59:60",
+            "2016/12/31 23:59:60",
+            "Dec 31, 2016 23:59:60",
+            "31 Dec 2016 23:59:60",
+            "Dec 31, 2016 23:59:60 UTC",
+            "Sat Dec 31 23:59:60 2016",
         ];
 
-        for &(input, want) in test_cases.iter() {
+        for &input in test_cases.iter() {
+            parse.leap_second(LeapSecond::Preserve);
             assert_eq!(
-                parse.month_mdy_hms_z(input).unwrap().unwrap(),
-                want,
-                "month_mdy_hms_z/{}",
+                parse.parse(input).unwrap(),
+                Utc.ymd(2016, 12, 31)
+                    .and_hms_nano(23, 59, 59, 1_000_000_000),
+                "leap_second/preserve/{}",
                 input
-            )
+            );
+            parse.leap_second(LeapSecond::Clamp);
+            assert_eq!(
+                parse.parse(input).unwrap(),
+                Utc.ymd(2016, 12, 31).and_hms_nano(23, 59, 59, 999_999_999),
+                "leap_second/clamp/{}",
+                input
+            );
+            parse.leap_second(LeapSecond::Reject);
+            assert!(parse.parse(input).is_err(), "leap_second/reject/{}", input);
         }
-        assert!(parse.month_mdy_hms_z("not-date-time").is_none());
     }
 
     #[test]
-    fn month_mdy() {
+    fn time_of_day() {
         let parse = Parse::new(&Utc, Utc::now().time());
 
         let test_cases = [
+            ("2021-05-14 1430", Utc.ymd(2021, 5, 14).and_hms(14, 30, 0)),
+            ("2021-05-14 0005", Utc.ymd(2021, 5, 14).and_hms(0, 5, 0)),
+            ("2021-05-14 14h30", Utc.ymd(2021, 5, 14).and_hms(14, 30, 0)),
+            ("2021-05-14 14H", Utc.ymd(2021, 5, 14).and_hms(14, 0, 0)),
+            ("05/14/2021 14.30", Utc.ymd(2021, 5, 14).and_hms(14, 30, 0)),
+            ("05/14/2021 14.30 ", Utc.ymd(2021, 5, 14).and_hms(14, 30, 0)),
             (
-                "May 25, 2021",
-                Utc.ymd(2021, 5, 25).and_time(Utc::now().time()),
+                "12 Feb 2006 19:17 ",
+                Utc.ymd(2006, 2, 12).and_hms(19, 17, 0),
             ),
             (
-                "oct 7, 1970",
-                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+                "2021/05/14 2.30 pm",
+                Utc.ymd(2021, 5, 14).and_hms(14, 30, 0),
             ),
             (
-                "oct 7, 70",
-                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+                "May 14, 2021 2:30 p.m.",
+                Utc.ymd(2021, 5, 14).and_hms(14, 30, 0),
             ),
             (
-                "oct. 7, 1970",
-                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+                "May 14, 2021 at 2:30:15 P.M.",
+                Utc.ymd(2021, 5, 14).and_hms(14, 30, 15),
             ),
             (
-                "oct. 7, 70",
-                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+                "14 May 2021 2:30 Pm",
+                Utc.ymd(2021, 5, 14).and_hms(14, 30, 0),
             ),
+            ("14 May 2021 2 pM", Utc.ymd(2021, 5, 14).and_hms(14, 0, 0)),
+            ("2021-05-14 9 a.m.", Utc.ymd(2021, 5, 14).and_hms(9, 0, 0)),
+            ("2021-05-14 noon", Utc.ymd(2021, 5, 14).and_hms(12, 0, 0)),
+            ("2021-05-14 12 noon", Utc.ymd(2021, 5, 14).and_hms(12, 0, 0)),
+            ("05/14/2021 Midnight", Utc.ymd(2021, 5, 14).and_hms(0, 0, 0)),
             (
-                "October 7, 1970",
-                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
+                "2021-05-14 12 midnight PDT",
+                Utc.ymd(2021, 5, 14).and_hms(7, 0, 0),
+            ),
+            (
+                "2021-05-14 1430 UTC",
+                Utc.ymd(2021, 5, 14).and_hms(14, 30, 0),
+            ),
+            (
+                "May 14, 2021 2:30 p.m. PDT",
+                Utc.ymd(2021, 5, 14).and_hms(21, 30, 0),
             ),
         ];
 
         for &(input, want) in test_cases.iter() {
-            assert_eq!(
-                parse
-                    .month_mdy(input)
-                    .unwrap()
-                    .unwrap()
-                    .trunc_subsecs(0)
-                    .with_second(0)
-                    .unwrap(),
-                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
-                "month_mdy/{}",
-                input
-            )
+            assert_eq!(parse.parse(input).unwrap(), want, "time_of_day/{}", input)
         }
-        assert!(parse.month_mdy("not-date-time").is_none());
+        assert!(parse.parse("2021-05-14 14").is_err());
+        assert!(parse.parse("2021-05-14 2460").is_err());
+        assert!(parse.parse("2021-05-14 2021").is_err());
+        assert!(parse.parse("May 14 2021").is_err());
+        assert!(parse.parse("2021-05-14 14h75").is_err());
+        assert!(parse.parse("2021-05-14 24.00").is_err());
+        assert!(parse.parse("2021-05-14 2 pm ").is_err());
+        assert!(parse.parse("not-date-time").is_err());
     }
 
     #[test]
-    fn month_dmy_hms() {
+    fn parse_bytes() {
         let parse = Parse::new(&Utc, Utc::now().time());
 
-        let test_cases = [
+        let test_cases: [(&[u8], _); 2] = [
             (
-                "12 Feb 2006, 19:17",
-                Utc.ymd(2006, 2, 12).and_hms(19, 17, 0),
+                b"2021-04-30 21:14:10",
+                Utc.ymd(2021, 4, 30).and_hms(21, 14, 10),
             ),
-            ("12 Feb 2006 19:17", Utc.ymd(2006, 2, 12).and_hms(19, 17, 0)),
             (
-                "14 May 2019 19:11:40.164",
-                Utc.ymd(2019, 5, 14).and_hms_milli(19, 11, 40, 164),
+                b"May 02, 2021 15:51:31 UTC",
+                Utc.ymd(2021, 5, 2).and_hms(15, 51, 31),
             ),
         ];
 
         for &(input, want) in test_cases.iter() {
             assert_eq!(
-                parse.month_dmy_hms(input).unwrap().unwrap(),
+                parse.parse_bytes(input).unwrap(),
                 want,
-                "month_dmy_hms/{}",
-                input
+                "parse_bytes/{}",
+                String::from_utf8_lossy(input)
             )
         }
-        assert!(parse.month_dmy_hms("not-date-time").is_none());
+        let err = parse
+            .parse_bytes("14 mai 2021 à midi".as_bytes())
+            .unwrap_err();
+        assert!(err.to_string().ends_with("did not match any formats."));
+        let err = parse.parse_bytes(b"2021-04-30 \xff21:14:10").unwrap_err();
+        assert!(err.to_string().ends_with("is not valid UTF-8."));
     }
 
     #[test]
-    fn month_dmy() {
+    fn parse_many() {
         let parse = Parse::new(&Utc, Utc::now().time());
 
-        let test_cases = [
-            ("7 oct 70", Utc.ymd(1970, 10, 7).and_time(Utc::now().time())),
-            (
-                "7 oct 1970",
-                Utc.ymd(1970, 10, 7).and_time(Utc::now().time()),
-            ),
-            (
-                "03 February 2013",
-                Utc.ymd(2013, 2, 3).and_time(Utc::now().time()),
-            ),
-            (
-                "1 July 2013",
-                Utc.ymd(2013, 7, 1).and_time(Utc::now().time()),
-            ),
+        let inputs = ["1620021848", "2021", "not-a-date", "1620021849"];
+        let mut out = [None; 4];
+        assert_eq!(parse.parse_many(inputs, &mut out), 3);
+        assert_eq!(
+            out,
+            [
+                Some(Utc.ymd(2021, 5, 3).and_hms(6, 4, 8)),
+                // the year 2021, as on its own, even after unix timestamps
+                Some(Utc.ymd(2021, 1, 1).and_hms(0, 0, 0)),
+                None,
+                Some(Utc.ymd(2021, 5, 3).and_hms(6, 4, 9)),
+            ]
+        );
+
+        // the hint never changes how an input reads
+        let inputs = [
+            "14 May 2021 18:51:00 GMT",
+            "14 May 2021",
+            "Fri, 14 May 2021 18:51:00 GMT",
+            "14 May 2021 18:51:00 GMT",
+            "1620021848",
+            "202105",
+            "2021",
+            "2021-05",
+            "2021-05-14",
+            "2021-05",
         ];
+        let mut out = [None; 10];
+        assert_eq!(parse.parse_many(inputs, &mut out), 10);
+        for (input, parsed) in inputs.iter().zip(out) {
+            assert_eq!(parsed, parse.parse(input).ok(), "parse_many/{}", input);
+        }
 
-        for &(input, want) in test_cases.iter() {
+        let inputs = [
+            "2021-04-30 21:14:10",
+            "",
+            "2021-04-30 21:14:11",
+            "9999-12-31 23:59:59",
+        ]
+        .repeat(3);
+        let mut values = [-1; 12];
+        let mut validity = [0xff; 2];
+        assert_eq!(
+            parse.parse_many_nanos(&inputs, &mut values, &mut validity),
+            6
+        );
+        assert_eq!(validity, [0b0101_0101, 0b1111_0101]);
+        assert_eq!(values[0], 1_619_817_250_000_000_000);
+        assert_eq!(values[1], 0);
+        assert_eq!(values[3], 0);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_parse_many() {
+        let parse = Parse::new(&Utc, Utc::now().time());
+
+        let inputs: Vec<String> = (0..10_000)
+            .map(|i| match i % 3 {
+                0 => format!("{}", 1_620_021_848 + i),
+                1 => "2021-04-30 21:14:10".to_string(),
+                _ => "not-a-date".to_string(),
+            })
+            .collect();
+        let mut out = vec![None; inputs.len()];
+        let mut sequential = vec![None; inputs.len()];
+        assert_eq!(
+            parse.par_parse_many(&inputs, &mut out),
+            parse.parse_many(&inputs, &mut sequential)
+        );
+        assert_eq!(out, sequential);
+
+        let mut values = vec![0; inputs.len()];
+        let mut validity = vec![0; inputs.len().div_ceil(8)];
+        assert_eq!(
+            parse.par_parse_many_nanos(&inputs, &mut values, &mut validity),
+            6667
+        );
+        assert_eq!(validity[0], 0b1101_1011);
+
+        // values that read differently after a unix timestamp, on both sides of a chunk boundary
+        let mut inputs: Vec<String> = (0..2 * CHUNK)
+            .map(|i| format!("{}", 1_620_021_848 + i))
+            .collect();
+        for i in CHUNK - 2..CHUNK + 2 {
+            inputs[i] = ["2021", "2020"][i % 2].to_string();
+        }
+        let mut out = vec![None; inputs.len()];
+        let mut sequential = vec![None; inputs.len()];
+        assert_eq!(
+            parse.par_parse_many(&inputs, &mut out),
+            parse.parse_many(&inputs, &mut sequential)
+        );
+        assert_eq!(out, sequential);
+        assert_eq!(out[CHUNK], Some(Utc.ymd(2021, 1, 1).and_hms(0, 0, 0)));
+        assert_eq!(out[CHUNK + 1], Some(Utc.ymd(2020, 1, 1).and_hms(0, 0, 0)));
+    }
+
+    #[test]
+    fn dispatch() {
+        let mut parse = Parse::new(&Utc, Utc::now().time());
+        parse.reference_time(Utc.ymd(2021, 6, 15).and_hms(12, 0, 0));
+        let inputs = [
+            "1511648546",
+            "1620021848429",
+            "1620024872717915000",
+            "0",
+            "-770172300",
+            "1671673426.123456789",
+            "2021-05-01T01:17:02.604456Z",
+            "2017-11-25T22:34:50Z",
+            "Wed, 02 Jun 2021 06:31:39 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "2014-04-26 05:24:37 PM",
+            "2021-04-30 21:14",
+            "2021-04-30 21:14:10",
+            "2021-04-30 21:14:10.052282",
+            "2014-04-26 17:24:37.123",
+            "2014-04-26 17:24:37.3186369",
+            "2012-08-03 18:31:59.257000000",
+            "2017-11-25 13:31:15 PST",
+            "2017-11-25 13:31 PST",
+            "2014-12-16 06:20:00 UTC",
+            "2014-12-16 06:20:00 GMT",
+            "2014-04-26 13:13:43 +0800",
+            "2014-04-26 13:13:44 +09:00",
+            "2012-08-03 18:31:59.257000000 +0000",
+            "2015-09-30 18:48:56.35272715 UTC",
+            "2021-05-14 24:00:00",
+            "2016-12-31 23:59:60",
+            "2021-05-14 1430",
+            "2021-05-14 14h30",
+            "May 14, 2021 2:30 p.m.",
+            "2021-05-14 12 noon",
+            "2021-02-21",
+            "2021-02-21 PST",
+            "2021-02-21 UTC",
+            "2020-07-20+08:00",
+            "May 8, 2009 5:57:51 PM",
+            "September 17, 2012 10:09am",
+            "September 17, 2012, 10:10:09",
+            "May 02, 2021 15:51:31 UTC",
+            "May 02, 2021 15:51 UTC",
+            "May 26, 2021, 12:49 AM PDT",
+            "September 17, 2012 at 10:09am PST",
+            "2021-Feb-21",
+            "May 25, 2021",
+            "oct 7, 1970",
+            "oct 7, 70",
+            "oct. 7, 1970",
+            "oct. 7, 70",
+            "October 7, 1970",
+            "12 Feb 2006, 19:17",
+            "12 Feb 2006 19:17",
+            "14 May 2019 19:11:40.164",
+            "7 oct 70",
+            "7 oct 1970",
+            "03 February 2013",
+            "1 July 2013",
+            "4/8/2014 22:05",
+            "04/08/2014 22:05",
+            "4/8/14 22:05",
+            "04/2/2014 03:00:51",
+            "8/8/1965 12:00:00 AM",
+            "8/8/1965 01:00:01 PM",
+            "8/8/1965 01:00 PM",
+            "8/8/1965 1:00 PM",
+            "8/8/1965 12:00 AM",
+            "4/02/2014 03:00:51",
+            "03/19/2012 10:11:59",
+            "03/19/2012 10:11:59.3186369",
+            "3/31/2014",
+            "03/31/2014",
+            "08/21/71",
+            "8/1/71",
+            "2014/4/8 22:05",
+            "2014/04/08 22:05",
+            "2014/04/2 03:00:51",
+            "2014/4/02 03:00:51",
+            "2012/03/19 10:11:59",
+            "2012/03/19 10:11:59.3186369",
+            "2014/3/31",
+            "2014/03/31",
+            "May 27 02:45:27",
+            "Jun  2 06:31:39",
+            "Wed Jun  2 06:31:39 2021",
+            "Wed Jun  2 06:31:39 PDT 2021",
+            "[14/May/2021:18:51:00 -0700]",
+            "14/May/2021:18:51:00 +0000",
+            "2021",
+            "2021-05",
+            "May 2021",
+            "05/2021",
+            "May 14",
+            "2021Q2",
+            "Q2 2021",
+            "2021-Q2",
+            "H1 2021",
+            "FY2022 Q1",
+            "H1 FY2022",
+            "02 Jun 2021 06:31:39 GMT",
+            "1.2.3",
+            "2014.03.30",
+            "171113 14:14:20",
+            "not-a-date",
+            "",
+            "2021-05-14 noon",
+            "May 1-31, 2021",
+            "Tuesday, 14-May-21 18:51:00 PDT",
+        ];
+
+        for input in inputs {
+            let mut other = lex(input);
+            other.shape = Shape::Other;
+            let dispatched = parse.detect(&lex(input), &mut None);
+            let cascaded = parse.detect(&other, &mut None);
             assert_eq!(
-                parse
-                    .month_dmy(input)
-                    .unwrap()
-                    .unwrap()
-                    .trunc_subsecs(0)
-                    .with_second(0)
-                    .unwrap(),
-                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
-                "month_dmy/{}",
+                dispatched.and_then(Result::ok),
+                cascaded.and_then(Result::ok),
+                "dispatch/{}",
                 input
             )
         }
-        assert!(parse.month_dmy("not-date-time").is_none());
     }
 
     #[test]
-    fn slash_mdy_hms() {
-        let parse = Parse::new(&Utc, Utc::now().time());
-
-        let test_cases = vec![
-            ("4/8/2014 22:05", Utc.ymd(2014, 4, 8).and_hms(22, 5, 0)),
-            ("04/08/2014 22:05", Utc.ymd(2014, 4, 8).and_hms(22, 5, 0)),
-            ("4/8/14 22:05", Utc.ymd(2014, 4, 8).and_hms(22, 5, 0)),
-            ("04/2/2014 03:00:51", Utc.ymd(2014, 4, 2).and_hms(3, 0, 51)),
-            ("8/8/1965 12:00:00 AM", Utc.ymd(1965, 8, 8).and_hms(0, 0, 0)),
-            (
-                "8/8/1965 01:00:01 PM",
-                Utc.ymd(1965, 8, 8).and_hms(13, 0, 1),
-            ),
-            ("8/8/1965 01:00 PM", Utc.ymd(1965, 8, 8).and_hms(13, 0, 0)),
-            ("8/8/1965 1:00 PM", Utc.ymd(1965, 8, 8).and_hms(13, 0, 0)),
-            ("8/8/1965 12:00 AM", Utc.ymd(1965, 8, 8).and_hms(0, 0, 0)),
-            ("4/02/2014 03:00:51", Utc.ymd(2014, 4, 2).and_hms(3, 0, 51)),
-            (
-                "03/19/2012 10:11:59",
-                Utc.ymd(2012, 3, 19).and_hms(10, 11, 59),
-            ),
-            (
-                "03/19/2012 10:11:59.3186369",
-                Utc.ymd(2012, 3, 19).and_hms_nano(10, 11, 59, 318636900),
-            ),
+    fn custom_format() {
+        let tz = FixedOffset::west_opt(4 * 3600).unwrap();
+        let mut parse = Parse::new(&tz, NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+        parse
+            .custom_format("%d-%b-%Y %Hh%M")
+            .unwrap()
+            .custom_format("%Y.%m.%d-%H:%M:%S %Z")
+            .unwrap()
+            .custom_format("%Y.%m.%d-%H:%M:%S%z")
+            .unwrap()
+            .custom_format("%Y.%m.%d")
+            .unwrap()
+            .custom_format("%d|%m|%y")
+            .unwrap();
+        let test_cases = [
+            ("14-May-2021 18h51", "2021-05-14T22:51:00Z"),
+            ("2021.05.14-18:51:00 PDT", "2021-05-15T01:51:00Z"),
+            ("2021.05.14-18:51:00 UTC", "2021-05-14T18:51:00Z"),
+            ("2021.05.14-18:51:00+0900", "2021-05-14T09:51:00Z"),
+            ("2021.05.14", "2021-05-14T09:00:00Z"),
+            ("14|05|21", "2021-05-14T09:00:00Z"),
+            // the accepted formats still come first
+            ("2021-05-14 18:51:00", "2021-05-14T22:51:00Z"),
         ];
 
         for &(input, want) in test_cases.iter() {
             assert_eq!(
-                parse.slash_mdy_hms(input).unwrap().unwrap(),
+                parse
+                    .parse(input)
+                    .unwrap()
+                    .to_rfc3339_opts(SecondsFormat::AutoSi, true),
                 want,
-                "slash_mdy_hms/{}",
+                "custom_format/{}",
                 input
-            )
+            );
         }
-        assert!(parse.slash_mdy_hms("not-date-time").is_none());
+        assert_eq!(
+            parse.explain("14-May-2021 18h51").unwrap().1,
+            "custom format"
+        );
+        // an unknown abbreviation is UTC, as in the accepted formats
+        assert_eq!(
+            parse.parse("2021.05.14-18:51:00 XYZ").unwrap(),
+            Utc.ymd(2021, 5, 14).and_hms(18, 51, 0)
+        );
+        assert!(parse.parse("2021.05.14-18:51:00 +99:99").is_err());
+        assert!(parse.parse("2021.13.14").is_err());
     }
 
     #[test]
-    fn slash_mdy() {
-        let parse = Parse::new(&Utc, Utc::now().time());
+    fn custom_format_priority() {
+        let mut parse = Parse::new(&Utc, NaiveTime::MIN);
+        // read as dd/mm/yyyy instead of the accepted mm/dd/yyyy
+        parse.custom_format_with("%d/%m/%Y", None, 1).unwrap();
+        assert_eq!(
+            parse.parse("01/07/2021").unwrap(),
+            Utc.ymd(2021, 7, 1).and_hms(0, 0, 0)
+        );
+        // only when the prefilter matches
+        let mut parse = Parse::new(&Utc, NaiveTime::MIN);
+        parse
+            .custom_format_with("%d/%m/%Y", Some(r"^\d{2}/\d{2}/20"), 1)
+            .unwrap();
+        assert_eq!(
+            parse.parse("01/07/2021").unwrap(),
+            Utc.ymd(2021, 7, 1).and_hms(0, 0, 0)
+        );
+        assert_eq!(
+            parse.parse("01/07/1999").unwrap(),
+            Utc.ymd(1999, 1, 7).and_hms(0, 0, 0)
+        );
+        // priority 0 is tried after the accepted formats
+        let mut parse = Parse::new(&Utc, NaiveTime::MIN);
+        parse.custom_format("%d/%m/%Y").unwrap();
+        assert_eq!(
+            parse.parse("01/07/2021").unwrap(),
+            Utc.ymd(2021, 1, 7).and_hms(0, 0, 0)
+        );
+        // higher priorities first, then in the order they were registered
+        let mut parse = Parse::new(&Utc, NaiveTime::MIN);
+        parse
+            .custom_format_with("%Y %d %m", None, 1)
+            .unwrap()
+            .custom_format_with("%Y %m %d", None, 2)
+            .unwrap();
+        assert_eq!(
+            parse.parse("2021 11 01").unwrap(),
+            Utc.ymd(2021, 11, 1).and_hms(0, 0, 0)
+        );
 
+        assert_eq!(
+            parse.custom_format("%Y-%Q").err().unwrap().to_string(),
+            "%Y-%Q is not a valid strftime format."
+        );
+        assert!(parse.custom_format_with("%Y", Some("("), 0).is_err());
+    }
+
+    #[test]
+    fn explain() {
+        let parse = Parse::new(&Utc, Utc::now().time());
         let test_cases = [
+            ("1620021848", "unix timestamp"),
+            ("2021-04-30 21:14:10", "yyyy-mm-dd"),
+            ("2021-05-14 14h30", "yyyy-mm-dd"),
             (
-                "3/31/2014",
-                Utc.ymd(2014, 3, 31).and_time(Utc::now().time()),
-            ),
-            (
-                "03/31/2014",
-                Utc.ymd(2014, 3, 31).and_time(Utc::now().time()),
+                "Wed, 02 Jun 2021 06:31:39 GMT",
+                "rfc2822, rfc850 or asctime",
             ),
-            ("08/21/71", Utc.ymd(1971, 8, 21).and_time(Utc::now().time())),
-            ("8/1/71", Utc.ymd(1971, 8, 1).and_time(Utc::now().time())),
+            ("May 8, 2009 5:57:51 PM", "Mon dd, yyyy"),
+            ("7 oct 1970", "dd Mon yyyy"),
+            ("2021-Feb-21", "yyyy-mon-dd"),
+            ("4/8/2014 22:05", "mm/dd/yyyy or dd/mm/yyyy"),
+            ("2014/4/8 22:05", "yyyy/mm/dd"),
+            ("Mon Jan  2 15:04:05 MST 2006", "unix date"),
+            ("10/Oct/2000:13:55:36 -0700", "common log format"),
+            ("2021-05", "partial date"),
         ];
 
         for &(input, want) in test_cases.iter() {
-            assert_eq!(
-                parse
-                    .slash_mdy(input)
-                    .unwrap()
-                    .unwrap()
-                    .trunc_subsecs(0)
-                    .with_second(0)
-                    .unwrap(),
-                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
-                "slash_mdy/{}",
-                input
-            )
+            let (parsed, family) = parse.explain(input).unwrap();
+            assert_eq!(parsed, parse.parse(input).unwrap(), "parsed/{}", input);
+            assert_eq!(family, want, "family/{}", input);
         }
-        assert!(parse.slash_mdy("not-date-time").is_none());
+        assert!(parse.explain("not-a-date").is_err());
     }
 
     #[test]
-    fn slash_dmy() {
-        let mut parse = Parse::new(&Utc, Utc::now().time());
-
+    fn detect_format() {
+        let tz = FixedOffset::east_opt(3600).unwrap();
+        let mut parse = Parse::new_with_preference(&tz, NaiveTime::MIN, true);
+        parse.reference_time(Utc.ymd(2021, 6, 15).and_hms(12, 0, 0));
         let test_cases = [
+            ("1511648546", Some("%s")),
+            ("1671673426.123456789", Some("%s%.9f")),
             (
-                "31/3/2014",
-                Utc.ymd(2014, 3, 31).and_time(Utc::now().time()),
+                "2021-05-01T01:17:02.604456Z",
+                Some("%Y-%m-%dT%H:%M:%S%.6fZ"),
             ),
             (
-                "13/11/2014",
-                Utc.ymd(2014, 11, 13).and_time(Utc::now().time()),
+                "Wed, 02 Jun 2021 06:31:39 GMT",
+                Some("%a, %d %b %Y %H:%M:%S %Z"),
             ),
-            ("21/08/71", Utc.ymd(1971, 8, 21).and_time(Utc::now().time())),
-            ("1/8/71", Utc.ymd(1971, 8, 1).and_time(Utc::now().time())),
+            (
+                "Sunday, 06-Nov-94 08:49:37 GMT",
+                Some("%A, %d-%b-%y %H:%M:%S %Z"),
+            ),
+            ("2014-04-26 05:24:37 PM", Some("%Y-%m-%d %I:%M:%S %p")),
+            ("2014-04-26 17:24:37.3186369", Some("%Y-%m-%d %H:%M:%S%.f")),
+            ("2014-04-26 13:13:44 +09:00", Some("%Y-%m-%d %H:%M:%S %:z")),
+            (
+                "2012-08-03 18:31:59.257000000 +0000",
+                Some("%Y-%m-%d %H:%M:%S%.9f %z"),
+            ),
+            ("2020-07-20+08:00", Some("%Y-%m-%d%:z")),
+            ("2021-02-21 PST", Some("%Y-%m-%d %Z")),
+            ("2021-05-14 1430", Some("%Y-%m-%d %H%M")),
+            ("2021-05-14 14h30", Some("%Y-%m-%d %Hh%M")),
+            (
+                "September 17, 2012 at 10:09am PST",
+                Some("%B %d, %Y at %I:%M%P %Z"),
+            ),
+            ("oct. 7, 70", Some("%b. %-d, %y")),
+            ("14 May 2019 19:11:40.164", Some("%d %b %Y %H:%M:%S%.3f")),
+            // day first, as preferred
+            ("01/07/2021 10:00", Some("%d/%m/%Y %H:%M")),
+            ("4/8/14 22:05", Some("%-d/%-m/%y %H:%M")),
+            // the same day and month
+            ("03/03/2021", Some("%d/%m/%Y")),
+            ("3/3/21 22:05", Some("%-d/%-m/%y %H:%M")),
+            ("2014/4/02 03:00:51", Some("%Y/%-m/%d %H:%M:%S")),
+            (
+                "Wed Jun  2 06:31:39 PDT 2021",
+                Some("%a %b  %-d %H:%M:%S %Z %Y"),
+            ),
+            (
+                "[14/May/2021:18:51:00 -0700]",
+                Some("[%d/%b/%Y:%H:%M:%S %z]"),
+            ),
+            ("May 2021", Some("%b %Y")),
+            ("2021-05", Some("%Y-%m")),
+            // no strftime equivalent
+            ("2021-05-14 noon", None),
+            ("May 14, 2021 2:30 p.m.", None),
+            ("2021-05-14 24:00:00", None),
+            ("Q2 2021", None),
+            ("May 14", None),
+            ("not-a-date", None),
         ];
 
         for &(input, want) in test_cases.iter() {
+            let pattern = parse.detect_format(input);
+            assert_eq!(pattern.as_deref(), want, "detect_format/{}", input);
+            // the pattern reads the input back as the same date and time
+            let Some(pattern) = pattern.filter(|pattern| pattern.contains(":%M")) else {
+                continue;
+            };
+            let parsed = DateTime::parse_from_str(input, &pattern)
+                .map(|parsed| parsed.naive_local())
+                .or_else(|_| NaiveDateTime::parse_from_str(input, &pattern));
             assert_eq!(
-                parse
-                    .prefer_dmy(true)
-                    .slash_dmy(input)
-                    .unwrap()
-                    .unwrap()
-                    .trunc_subsecs(0)
-                    .with_second(0)
-                    .unwrap(),
-                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
-                "slash_dmy/{}",
+                parsed.unwrap_or_else(|err| panic!("{}: {}", input, err)),
+                parse.parse_fixed_offset(input).unwrap().naive_local(),
+                "parse_from_str/{}",
                 input
-            )
+            );
         }
-        assert!(parse.slash_dmy("not-date-time").is_none());
     }
 
     #[test]
-    fn slash_ymd_hms() {
-        let parse = Parse::new(&Utc, Utc::now().time());
-
+    fn parse_with_template() {
+        let parse = Parse::new(&Utc, NaiveTime::MIN);
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        // each input, rendered 40 days and 7 hours later, then in Tokyo
         let test_cases = [
-            ("2014/4/8 22:05", Utc.ymd(2014, 4, 8).and_hms(22, 5, 0)),
-            ("2014/04/08 22:05", Utc.ymd(2014, 4, 8).and_hms(22, 5, 0)),
-            ("2014/04/2 03:00:51", Utc.ymd(2014, 4, 2).and_hms(3, 0, 51)),
-            ("2014/4/02 03:00:51", Utc.ymd(2014, 4, 2).and_hms(3, 0, 51)),
             (
-                "2012/03/19 10:11:59",
-                Utc.ymd(2012, 3, 19).and_hms(10, 11, 59),
+                "2021-05-01T01:17:02.604Z",
+                "2021-06-10T08:17:02.604Z",
+                "2021-06-10T17:17:02.604+09:00",
             ),
             (
-                "2012/03/19 10:11:59.3186369",
-                Utc.ymd(2012, 3, 19).and_hms_nano(10, 11, 59, 318636900),
+                "Wed, 02 Jun 2021 06:31:39 GMT",
+                "Mon, 12 Jul 2021 13:31:39 GMT",
+                "Mon, 12 Jul 2021 22:31:39 +09:00",
+            ),
+            (
+                "2021-05-14 18:51 PDT",
+                "2021-06-24 01:51 PDT",
+                "2021-06-24 17:51 +09:00",
+            ),
+            (
+                "2014-04-26 13:13:44 +09:00",
+                "2014-06-05 20:13:44 +09:00",
+                "2014-06-05 20:13:44 +09:00",
+            ),
+            (
+                "may 8, 2009 5:57:51 PM",
+                "jun 18, 2009 12:57:51 AM",
+                "jun 18, 2009 9:57:51 AM",
+            ),
+            (
+                "SEPTEMBER 17, 2012 at 10:09am",
+                "OCTOBER 27, 2012 at 05:09pm",
+                "OCTOBER 28, 2012 at 02:09am",
+            ),
+            ("3/1/2021 7:05", "4/10/2021 14:05", "4/10/2021 23:05"),
+            (
+                "2021/05/14 18:51:00.25",
+                "2021/06/24 01:51:00.25",
+                "2021/06/24 10:51:00.25",
             ),
+            ("1511648546", "1515129746", "1515129746"),
+            ("May 2021", "Jun 2021", "Jun 2021"),
         ];
 
-        for &(input, want) in test_cases.iter() {
+        for &(input, shifted_input, local) in test_cases.iter() {
+            let (parsed, template) = parse.parse_with_template(input).unwrap();
+            let template = template.unwrap_or_else(|| panic!("template/{}", input));
+            let shifted = parsed + chrono::Duration::hours(40 * 24 + 7);
+            assert_eq!(template.render(&shifted), shifted_input, "render/{}", input);
             assert_eq!(
-                parse.slash_ymd_hms(input).unwrap().unwrap(),
-                want,
-                "slash_ymd_hms/{}",
+                template.render(&shifted.with_timezone(&tokyo)),
+                local,
+                "render/{}",
                 input
-            )
+            );
+            assert_eq!(template.render(&parsed), input, "render/{}", input);
         }
-        assert!(parse.slash_ymd_hms("not-date-time").is_none());
+
+        let (_, template) = parse.parse_with_template("2021-05-14 noon").unwrap();
+        assert!(template.is_none());
+        assert!(parse.parse_with_template("not-a-date").is_err());
     }
 
     #[test]
-    fn slash_ymd() {
-        let parse = Parse::new(&Utc, Utc::now().time());
-
+    fn parse_naive() {
+        // the wall-clock time of an input without an offset doesn't depend on the timezone, even
+        // when it is repeated by a DST transition there
+        let tz = chrono_tz::America::New_York;
+        let parse = Parse::new(&tz, NaiveTime::MIN);
+        let naive =
+            |input: &str| NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S%.f").unwrap();
+        let date = |input: &str| NaiveDate::parse_from_str(input, "%Y-%m-%d").unwrap();
+        let offset = |input: &str| DateTime::parse_from_rfc3339(input).unwrap();
         let test_cases = [
             (
-                "2014/3/31",
-                Utc.ymd(2014, 3, 31).and_time(Utc::now().time()),
+                "2021-04-30 21:14:10",
+                Reading::Naive(naive("2021-04-30 21:14:10")),
             ),
             (
-                "2014/03/31",
-                Utc.ymd(2014, 3, 31).and_time(Utc::now().time()),
+                "2021-11-07 01:30:00",
+                Reading::Naive(naive("2021-11-07 01:30:00")),
+            ),
+            (
+                "2014-04-26 17:24:37.123",
+                Reading::Naive(naive("2014-04-26 17:24:37.123")),
+            ),
+            (
+                "May 8, 2009 5:57:51 PM",
+                Reading::Naive(naive("2009-05-08 17:57:51")),
+            ),
+            (
+                "2021-05-14 14h30",
+                Reading::Naive(naive("2021-05-14 14:30:00")),
+            ),
+            (
+                "2021-05-14 24:00:00",
+                Reading::Naive(naive("2021-05-15 00:00:00")),
+            ),
+            (
+                "2021-05-14 24:00",
+                Reading::Naive(naive("2021-05-15 00:00:00")),
+            ),
+            (
+                "2021-05-14 24:00:00 +0200",
+                Reading::Offset(offset("2021-05-15T00:00:00+02:00")),
+            ),
+            ("2021-02-21", Reading::NaiveDate(date("2021-02-21"))),
+            ("03/31/2014", Reading::NaiveDate(date("2014-03-31"))),
+            ("7 oct 1970", Reading::NaiveDate(date("1970-10-07"))),
+            ("May 2021", Reading::NaiveDate(date("2021-05-01"))),
+            (
+                "2021-05-14 18:51 PDT",
+                Reading::Offset(offset("2021-05-14T18:51:00-07:00")),
+            ),
+            (
+                "2014-04-26 13:13:44 +09:00",
+                Reading::Offset(offset("2014-04-26T13:13:44+09:00")),
+            ),
+            (
+                "2021-05-01T01:17:02.604456+02:00",
+                Reading::Offset(offset("2021-05-01T01:17:02.604456+02:00")),
+            ),
+            (
+                "Wed, 02 Jun 2021 06:31:39 -0400",
+                Reading::Offset(offset("2021-06-02T06:31:39-04:00")),
+            ),
+            (
+                "1620021848",
+                Reading::Offset(offset("2021-05-03T06:04:08+00:00")),
             ),
         ];
 
         for &(input, want) in test_cases.iter() {
             assert_eq!(
-                parse
-                    .slash_ymd(input)
-                    .unwrap()
-                    .unwrap()
-                    .trunc_subsecs(0)
-                    .with_second(0)
-                    .unwrap(),
-                want.unwrap().trunc_subsecs(0).with_second(0).unwrap(),
-                "slash_ymd/{}",
+                parse.parse_naive(input).unwrap(),
+                want,
+                "parse_naive/{}",
                 input
-            )
+            );
         }
-        assert!(parse.slash_ymd("not-date-time").is_none());
+        assert!(parse.parse_naive("not-a-date").is_err());
     }
 }
@@ -0,0 +1,316 @@
+use anyhow::{anyhow, Result};
+use chrono::Duration;
+
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+/// Tries to parse an elapsed time in one of the following forms. Return `Duration` if possible.
+///
+/// - ISO 8601 durations: `PT1H30M`, `P3DT4H`, `P2W`, `PT0.5S`, `-PT15M`
+/// - clock times: `01:30:00`, `1:30`, `36:00:00.250`
+/// - human forms: `1h 30m`, `90 min`, `2 hours, 15 minutes`, `1.5 days`
+///
+/// Years and months have no fixed length, so ISO 8601 durations that use them are rejected. The
+/// units go from the largest to the smallest, each at most once, so `PT1M1H` is rejected too.
+pub fn parse(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let (negative, rest) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        Some(_) => (false, s),
+        None => return Err(anyhow!("premature end of input")),
+    };
+
+    let nanos = if rest.starts_with(['P', 'p']) {
+        parse_iso8601(&rest[1..])?
+    } else if rest.contains(':') {
+        parse_clock(rest)?
+    } else {
+        parse_human(rest)?
+    };
+
+    let duration = Duration::nanoseconds(nanos);
+    Ok(if negative { -duration } else { duration })
+}
+
+#[inline]
+fn add(total: i64, nanos: i64) -> Result<i64> {
+    total
+        .checked_add(nanos)
+        .ok_or_else(|| anyhow!("input is out of range"))
+}
+
+// PnWnDTnHnMnS, with the leading `P` already consumed
+#[inline]
+fn parse_iso8601(s: &str) -> Result<i64> {
+    let err_invalid = "input contains invalid characters";
+
+    let mut nanos = 0;
+    let mut in_time = false;
+    let mut seen = false;
+    let mut seen_time = false;
+    // the scale of the last designator, as each one is smaller than the one before
+    let mut last = i64::MAX;
+    let mut rest = s;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix(['T', 't']) {
+            if in_time {
+                return Err(anyhow!(err_invalid));
+            }
+            in_time = true;
+            rest = after;
+            continue;
+        }
+        let (value, after) = number(rest)?;
+        let mut chars = after.chars();
+        let unit = chars
+            .next()
+            .ok_or_else(|| anyhow!("premature end of input"))?;
+        let scale = match (in_time, unit.to_ascii_uppercase()) {
+            (false, 'W') => 7 * 86_400 * NANOS_PER_SEC,
+            (false, 'D') => 86_400 * NANOS_PER_SEC,
+            (true, 'H') => 3_600 * NANOS_PER_SEC,
+            (true, 'M') => 60 * NANOS_PER_SEC,
+            (true, 'S') => NANOS_PER_SEC,
+            (false, 'Y' | 'M') => {
+                return Err(anyhow!("years and months have no fixed length"));
+            }
+            _ => return Err(anyhow!(err_invalid)),
+        };
+        if scale >= last {
+            return Err(anyhow!("designators are repeated or out of order"));
+        }
+        last = scale;
+        nanos = add(nanos, value.nanos(scale)?)?;
+        seen = true;
+        seen_time = in_time;
+        rest = chars.as_str();
+    }
+
+    // a `T` is followed by at least one time component
+    if seen && in_time == seen_time {
+        Ok(nanos)
+    } else {
+        Err(anyhow!("premature end of input"))
+    }
+}
+
+// hh:mm, hh:mm:ss or hh:mm:ss.fff
+#[inline]
+fn parse_clock(s: &str) -> Result<i64> {
+    let err_invalid = "input contains invalid characters";
+    let err_out_of_range = "input is out of range";
+
+    let mut parts = s.split(':');
+    let (Some(hours), Some(minutes), seconds, None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(anyhow!(err_invalid));
+    };
+
+    let whole = |s: &str, scale: i64| -> Result<i64> {
+        if s.is_empty() || !s.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(anyhow!(err_invalid));
+        }
+        Number {
+            int: s,
+            fraction: "",
+        }
+        .nanos(scale)
+    };
+    let hours = whole(hours, 3_600 * NANOS_PER_SEC)?;
+    let minutes = whole(minutes, 60 * NANOS_PER_SEC)?;
+    if minutes >= 3_600 * NANOS_PER_SEC {
+        return Err(anyhow!(err_out_of_range));
+    }
+    let seconds = match seconds {
+        Some(seconds) => match number(seconds)? {
+            (seconds, "") => seconds.nanos(NANOS_PER_SEC)?,
+            _ => return Err(anyhow!(err_invalid)),
+        },
+        None => 0,
+    };
+    if seconds >= 60 * NANOS_PER_SEC {
+        return Err(anyhow!(err_out_of_range));
+    }
+    add(add(hours, minutes)?, seconds)
+}
+
+// a sequence of numbers with units, such as `1h 30m`, `90 min` or `2 hours and 5 minutes`
+#[inline]
+fn parse_human(s: &str) -> Result<i64> {
+    let mut nanos = 0;
+    let mut seen = false;
+    // the scale of the last unit, as each one is smaller than the one before
+    let mut last = i64::MAX;
+    let mut rest = s;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        if let Some(after) = rest.strip_prefix("and ") {
+            rest = after.trim_start();
+        }
+        if rest.is_empty() {
+            break;
+        }
+
+        let (value, after) = number(rest)?;
+        let after = after.trim_start();
+        let upto = after
+            .as_bytes()
+            .iter()
+            .position(|&c| !c.is_ascii_alphabetic())
+            .unwrap_or(after.len());
+        let scale = unit(&after[..upto])?;
+        if scale >= last {
+            return Err(anyhow!("units are repeated or out of order"));
+        }
+        last = scale;
+        nanos = add(nanos, value.nanos(scale)?)?;
+        seen = true;
+        rest = &after[upto..];
+    }
+
+    if seen {
+        Ok(nanos)
+    } else {
+        Err(anyhow!("premature end of input"))
+    }
+}
+
+/// Returns the number of nanoseconds in the named unit, compared case-insensitively.
+#[inline]
+fn unit(name: &str) -> Result<i64> {
+    let scale = match name.to_ascii_lowercase().as_str() {
+        "ns" | "nanosecond" | "nanoseconds" => 1,
+        "us" | "microsecond" | "microseconds" => 1_000,
+        "ms" | "msec" | "msecs" | "millisecond" | "milliseconds" => 1_000_000,
+        "s" | "sec" | "secs" | "second" | "seconds" => NANOS_PER_SEC,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60 * NANOS_PER_SEC,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 3_600 * NANOS_PER_SEC,
+        "d" | "day" | "days" => 86_400 * NANOS_PER_SEC,
+        "w" | "wk" | "wks" | "week" | "weeks" => 7 * 86_400 * NANOS_PER_SEC,
+        "" => return Err(anyhow!("missing unit")),
+        _ => return Err(anyhow!("unknown unit {}", name)),
+    };
+    Ok(scale)
+}
+
+/// A non-negative decimal number, as the digits of its integer part and of its fraction.
+#[derive(Clone, Copy)]
+struct Number<'a> {
+    int: &'a str,
+    fraction: &'a str,
+}
+
+impl Number<'_> {
+    /// Returns the number of nanoseconds in this many units of `scale` nanoseconds, rounded to the
+    /// nearest one.
+    fn nanos(self, scale: i64) -> Result<i64> {
+        let err_out_of_range = || anyhow!("input is out of range");
+        let digits = |s: &str| -> Result<i128> {
+            if s.is_empty() {
+                return Ok(0);
+            }
+            s.parse::<i128>().map_err(|_| err_out_of_range())
+        };
+        // digits past the 18th are below a nanosecond for every unit
+        let fraction = &self.fraction[..self.fraction.len().min(18)];
+        let denominator = 10_i128.pow(fraction.len() as u32);
+        let scale = i128::from(scale);
+        let fraction = (digits(fraction)? * scale * 2 + denominator) / (2 * denominator);
+        let nanos = digits(self.int)?
+            .checked_mul(scale)
+            .and_then(|nanos| nanos.checked_add(fraction))
+            .ok_or_else(err_out_of_range)?;
+        i64::try_from(nanos).map_err(|_| err_out_of_range())
+    }
+}
+
+/// Consumes a non-negative decimal number, with an optional fraction after `.` or `,`.
+#[inline]
+fn number(s: &str) -> Result<(Number<'_>, &str)> {
+    let bytes = s.as_bytes();
+    let int_end = bytes
+        .iter()
+        .position(|c| !c.is_ascii_digit())
+        .unwrap_or(bytes.len());
+    let mut end = int_end;
+    if matches!(bytes.get(end), Some(b'.' | b',')) {
+        let frac_len = bytes[end + 1..]
+            .iter()
+            .position(|c| !c.is_ascii_digit())
+            .unwrap_or(bytes.len() - end - 1);
+        if frac_len > 0 {
+            end += 1 + frac_len;
+        }
+    }
+    if int_end == 0 && end == 0 {
+        return Err(anyhow!("input contains invalid characters"));
+    }
+
+    let number = Number {
+        int: &s[..int_end],
+        fraction: s.get(int_end + 1..end).unwrap_or_default(),
+    };
+    Ok((number, &s[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let test_cases = [
+            ("PT1H30M", Duration::minutes(90)),
+            ("P3DT4H", Duration::hours(3 * 24 + 4)),
+            ("P2W", Duration::days(14)),
+            ("PT0.5S", Duration::milliseconds(500)),
+            ("PT1,5H", Duration::minutes(90)),
+            ("-PT15M", Duration::minutes(-15)),
+            (
+                "P10000DT0.000000001S",
+                Duration::days(10_000) + Duration::nanoseconds(1),
+            ),
+            ("PT0.0000000005S", Duration::nanoseconds(1)),
+            ("01:30:00", Duration::minutes(90)),
+            ("1:30", Duration::minutes(90)),
+            ("36:00:00.250", Duration::milliseconds(36 * 3_600_000 + 250)),
+            ("1h 30m", Duration::minutes(90)),
+            ("1h30m", Duration::minutes(90)),
+            ("90 min", Duration::minutes(90)),
+            ("2 hours, 15 minutes", Duration::minutes(135)),
+            ("1 day and 2 hours", Duration::hours(26)),
+            ("1.5 days", Duration::hours(36)),
+            ("250ms", Duration::milliseconds(250)),
+            ("45 Seconds", Duration::seconds(45)),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(super::parse(input).unwrap(), want, "parse/{}", input)
+        }
+
+        for input in [
+            "",
+            "P",
+            "PT",
+            "P1DT",
+            "P1M",
+            "P1Y2D",
+            "PT1D",
+            "PT1H1H",
+            "PT1M1H",
+            "P1D2D",
+            "1:60",
+            "1:30:75",
+            "1:2:3:4",
+            "90",
+            "90 lightyears",
+            "30m 1h",
+            "1 hour and 2 hours",
+            "300000 weeks",
+            "not-a-duration",
+        ] {
+            assert!(super::parse(input).is_err(), "parse/{}", input)
+        }
+    }
+}
@@ -0,0 +1,554 @@
+use chrono::format::{self, Parsed, StrftimeItems};
+use chrono::prelude::*;
+use std::fmt::Display;
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// How a word was capitalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Case {
+    Lower,
+    Upper,
+    Title,
+}
+
+impl Case {
+    fn of(word: &str) -> Case {
+        if word.bytes().all(|b| b.is_ascii_lowercase()) {
+            Case::Lower
+        } else if word.len() > 1 && word.bytes().all(|b| b.is_ascii_uppercase()) {
+            Case::Upper
+        } else {
+            Case::Title
+        }
+    }
+
+    fn apply(self, word: &str) -> String {
+        match self {
+            Case::Lower => word.to_ascii_lowercase(),
+            Case::Upper => word.to_ascii_uppercase(),
+            Case::Title => {
+                let lower = word.to_ascii_lowercase();
+                let mut chars = lower.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_ascii_uppercase().to_string() + chars.as_str()
+                })
+            }
+        }
+    }
+}
+
+/// One piece of an input, as it was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Field {
+    Literal(String),
+    Year,
+    ShortYear,
+    Month { padded: bool },
+    MonthName { abbreviated: bool, case: Case },
+    Day { padded: bool },
+    Weekday { abbreviated: bool, case: Case },
+    Hour { padded: bool, twelve: bool },
+    Minute,
+    Second,
+    Fraction { digits: usize },
+    Meridiem { case: Case },
+    Offset { colon: bool },
+    // a timezone abbreviation, such as PDT
+    Zone { name: String },
+    // the Z of UTC, as in 2021-05-01T01:17:02Z
+    Zulu,
+    Timestamp,
+}
+
+/// What the input was read as: its date and time as written, and its offset when it has one.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Expected {
+    pub(crate) local: NaiveDateTime,
+    pub(crate) offset: Option<i32>,
+    pub(crate) has_time: bool,
+    // whether a day and month written as numbers come day first, as in 14/05/2021
+    pub(crate) dmy: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Digits(&'a str),
+    Alpha(&'a str),
+    Other(char),
+}
+
+fn tokens(input: &str) -> Vec<Token<'_>> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        if bytes[i].is_ascii_digit() {
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            tokens.push(Token::Digits(&input[start..i]));
+        } else if bytes[i].is_ascii_alphabetic() {
+            while i < bytes.len() && bytes[i].is_ascii_alphabetic() {
+                i += 1;
+            }
+            tokens.push(Token::Alpha(&input[start..i]));
+        } else {
+            tokens.push(Token::Other(bytes[i] as char));
+            i += 1;
+        }
+    }
+    tokens
+}
+
+// Whether the word is the abbreviated or the full name of the month, taking May as abbreviated.
+fn month_name(word: &str, month: u32) -> Option<bool> {
+    let name = MONTHS[month as usize - 1];
+    if word.eq_ignore_ascii_case(&name[..3]) {
+        Some(true)
+    } else if word.eq_ignore_ascii_case(name) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn weekday_name(word: &str, weekday: Weekday) -> Option<bool> {
+    let name = WEEKDAYS[weekday.num_days_from_monday() as usize];
+    if word.eq_ignore_ascii_case(name) {
+        Some(false)
+    } else if word.eq_ignore_ascii_case(&name[..3]) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+#[derive(Default)]
+struct Assigned {
+    year: bool,
+    month: bool,
+    day: bool,
+    hour: bool,
+    minute: bool,
+    second: bool,
+    fraction: bool,
+    offset: bool,
+}
+
+// Appends a literal character, merging it with the literal before it.
+fn push_literal(fields: &mut Vec<Field>, c: char) {
+    if let Some(Field::Literal(literal)) = fields.last_mut() {
+        literal.push(c);
+    } else {
+        fields.push(Field::Literal(c.to_string()));
+    }
+}
+
+// Takes the last character of the literal before the current token, if it is `c`.
+fn pop_literal(fields: &mut Vec<Field>, c: char) -> bool {
+    let Some(Field::Literal(literal)) = fields.last_mut() else {
+        return false;
+    };
+    if !literal.ends_with(c) {
+        return false;
+    }
+    literal.pop();
+    if literal.is_empty() {
+        fields.pop();
+    }
+    true
+}
+
+/// Splits the input into the fields it was written with, matching each number and word to a part
+/// of what it was read as. `None` when a part of the input has no strftime equivalent, such as the
+/// `noon` of `2021-05-14 noon`, or doesn't match any part.
+pub(crate) fn fields(input: &str, expected: &Expected) -> Option<Vec<Field>> {
+    if !input.is_ascii() {
+        return None;
+    }
+    let local = expected.local;
+    let tokens = tokens(input);
+    let named_month = tokens.iter().any(|token| match token {
+        Token::Alpha(word) => month_name(word, local.month()).is_some(),
+        _ => false,
+    });
+    let twelve = tokens.iter().any(|token| match token {
+        Token::Alpha(word) => word.eq_ignore_ascii_case("am") || word.eq_ignore_ascii_case("pm"),
+        _ => false,
+    });
+    let hour12 = (local.hour() + 11) % 12 + 1;
+
+    let mut fields = Vec::new();
+    let mut done = Assigned::default();
+    let mut i = 0;
+    while i < tokens.len() {
+        match tokens[i] {
+            Token::Other('%') => {
+                push_literal(&mut fields, '%');
+                push_literal(&mut fields, '%');
+            }
+            Token::Other(c) => push_literal(&mut fields, c),
+            Token::Alpha(word) => {
+                let field = if let (false, Some(abbreviated)) =
+                    (done.month, month_name(word, local.month()))
+                {
+                    done.month = true;
+                    Field::MonthName {
+                        abbreviated,
+                        case: Case::of(word),
+                    }
+                } else if let Some(abbreviated) = weekday_name(word, local.weekday()) {
+                    Field::Weekday {
+                        abbreviated,
+                        case: Case::of(word),
+                    }
+                } else if twelve
+                    && (word.eq_ignore_ascii_case("am") || word.eq_ignore_ascii_case("pm"))
+                {
+                    if word[..1].eq_ignore_ascii_case("p") != (local.hour() >= 12) {
+                        return None;
+                    }
+                    Field::Meridiem {
+                        case: Case::of(word),
+                    }
+                } else if word == "Z" {
+                    Field::Zulu
+                } else if matches!(word, "T" | "h" | "at") {
+                    Field::Literal(word.to_string())
+                } else if (done.day || done.year) && (3..=5).contains(&word.len()) {
+                    Field::Zone {
+                        name: word.to_string(),
+                    }
+                } else {
+                    return None;
+                };
+                match field {
+                    Field::Literal(word) => word.chars().for_each(|c| push_literal(&mut fields, c)),
+                    field => fields.push(field),
+                }
+            }
+            Token::Digits(digits) => {
+                let value: u32 = digits.parse().ok()?;
+                let padded = digits.len() == 2;
+                // a fraction of a second, after the seconds
+                if done.second && !done.fraction && pop_literal(&mut fields, '.') {
+                    let nanos = format!("{:0<9}", &digits[..digits.len().min(9)]);
+                    if digits.len() > 9
+                        || nanos.parse::<u32>().ok()? != local.nanosecond() % 1_000_000_000
+                    {
+                        return None;
+                    }
+                    done.fraction = true;
+                    fields.push(Field::Fraction {
+                        digits: digits.len(),
+                    });
+                    i += 1;
+                    continue;
+                }
+                // an offset, after the date
+                if let (Some(offset), false, true) =
+                    (expected.offset, done.offset, done.day || done.hour)
+                {
+                    let colon = digits.len() == 2
+                        && tokens.get(i + 1) == Some(&Token::Other(':'))
+                        && matches!(tokens.get(i + 2), Some(Token::Digits(minutes)) if minutes.len() == 2);
+                    if colon || digits.len() == 4 {
+                        let sign = if pop_literal(&mut fields, '+') {
+                            1
+                        } else if pop_literal(&mut fields, '-') {
+                            -1
+                        } else {
+                            0
+                        };
+                        if sign != 0 {
+                            let (hours, minutes): (i32, i32) = if colon {
+                                let Token::Digits(minutes) = tokens[i + 2] else {
+                                    unreachable!()
+                                };
+                                (value as i32, minutes.parse().ok()?)
+                            } else {
+                                (value as i32 / 100, value as i32 % 100)
+                            };
+                            if sign * (hours * 3600 + minutes * 60) != offset {
+                                return None;
+                            }
+                            done.offset = true;
+                            fields.push(Field::Offset { colon });
+                            i += if colon { 3 } else { 1 };
+                            continue;
+                        }
+                    }
+                }
+
+                let date = done.year || done.month || done.day;
+                let field = if digits.len() == 4 && !done.year && value == local.year() as u32 {
+                    done.year = true;
+                    Field::Year
+                } else if digits.len() <= 2
+                    && !done.month
+                    && !named_month
+                    && value == local.month()
+                    && (!expected.dmy || done.day || value != local.day())
+                {
+                    done.month = true;
+                    Field::Month { padded }
+                } else if digits.len() <= 2 && !done.day && value == local.day() {
+                    done.day = true;
+                    Field::Day { padded }
+                } else if digits.len() == 2 && !done.year && value == local.year() as u32 % 100 {
+                    done.year = true;
+                    Field::ShortYear
+                } else if digits.len() <= 2
+                    && date
+                    && expected.has_time
+                    && !done.hour
+                    && value == if twelve { hour12 } else { local.hour() }
+                {
+                    done.hour = true;
+                    Field::Hour { padded, twelve }
+                } else if digits.len() == 2 && done.hour && !done.minute && value == local.minute()
+                {
+                    done.minute = true;
+                    Field::Minute
+                } else if digits.len() == 2
+                    && done.minute
+                    && !done.second
+                    && value == local.second()
+                {
+                    done.second = true;
+                    Field::Second
+                } else if digits.len() == 4
+                    && date
+                    && expected.has_time
+                    && !done.hour
+                    && !twelve
+                    && value == local.hour() * 100 + local.minute()
+                {
+                    // a military time such as 1430
+                    done.hour = true;
+                    done.minute = true;
+                    fields.push(Field::Hour {
+                        padded: true,
+                        twelve: false,
+                    });
+                    Field::Minute
+                } else {
+                    return None;
+                };
+                fields.push(field);
+            }
+        }
+        i += 1;
+    }
+    if !done.year || (expected.has_time && !done.hour) {
+        return None;
+    }
+    Some(fields)
+}
+
+/// The strftime pattern of the fields.
+pub(crate) fn strftime(fields: &[Field]) -> String {
+    let mut pattern = String::new();
+    for field in fields {
+        pattern.push_str(match field {
+            Field::Literal(literal) => literal,
+            Field::Year => "%Y",
+            Field::ShortYear => "%y",
+            Field::Month { padded: true } => "%m",
+            Field::Month { padded: false } => "%-m",
+            Field::MonthName {
+                abbreviated: true, ..
+            } => "%b",
+            Field::MonthName {
+                abbreviated: false, ..
+            } => "%B",
+            Field::Day { padded: true } => "%d",
+            Field::Day { padded: false } => "%-d",
+            Field::Weekday {
+                abbreviated: true, ..
+            } => "%a",
+            Field::Weekday {
+                abbreviated: false, ..
+            } => "%A",
+            Field::Hour {
+                padded: true,
+                twelve: false,
+            } => "%H",
+            Field::Hour {
+                padded: false,
+                twelve: false,
+            } => "%-H",
+            Field::Hour {
+                padded: true,
+                twelve: true,
+            } => "%I",
+            Field::Hour {
+                padded: false,
+                twelve: true,
+            } => "%-I",
+            Field::Minute => "%M",
+            Field::Second => "%S",
+            Field::Fraction { digits: 3 } => "%.3f",
+            Field::Fraction { digits: 6 } => "%.6f",
+            Field::Fraction { digits: 9 } => "%.9f",
+            Field::Fraction { .. } => "%.f",
+            Field::Meridiem { case: Case::Upper } => "%p",
+            Field::Meridiem { .. } => "%P",
+            Field::Offset { colon: true } => "%:z",
+            Field::Offset { colon: false } => "%z",
+            Field::Zone { .. } => "%Z",
+            Field::Zulu => "Z",
+            Field::Timestamp => "%s",
+        });
+    }
+    pattern
+}
+
+/// Whether chrono reads the input with the pattern as what it was expected to be.
+pub(crate) fn matches(input: &str, pattern: &str, expected: &Expected) -> bool {
+    let mut parsed = Parsed::new();
+    if format::parse(&mut parsed, input, StrftimeItems::new(pattern)).is_err() {
+        return false;
+    }
+    if parsed.offset().is_some() && parsed.offset() != expected.offset {
+        return false;
+    }
+    let local = expected.local;
+    if expected.has_time {
+        return parsed
+            .to_naive_datetime_with_offset(0)
+            .is_ok_and(|datetime| datetime == local);
+    }
+    match parsed.to_naive_date() {
+        Ok(date) => date == local.date(),
+        // a partial date, such as a year and a month
+        Err(_) => {
+            parsed.year() == Some(local.year())
+                && parsed.month().map_or(true, |month| month == local.month())
+                && parsed.day().is_none()
+        }
+    }
+}
+
+/// The fields of a unix timestamp, with the fraction of a second it was written with.
+pub(crate) fn timestamp(input: &str, expected: DateTime<FixedOffset>) -> Option<Vec<Field>> {
+    let fields = match input.split_once('.') {
+        None => vec![Field::Timestamp],
+        Some((_, fraction)) if (1..=9).contains(&fraction.len()) => vec![
+            Field::Timestamp,
+            Field::Fraction {
+                digits: fraction.len(),
+            },
+        ],
+        Some(_) => return None,
+    };
+    let mut parsed = Parsed::new();
+    format::parse(&mut parsed, input, StrftimeItems::new(&strftime(&fields))).ok()?;
+    // the fraction is read as a float, so only the seconds are exact
+    (parsed.timestamp() == Some(expected.timestamp())).then_some(fields)
+}
+
+/// Writes the datetime with the fields, in their case and with as many digits of a fraction of a
+/// second. A timezone abbreviation is kept while the datetime has the offset it was written with
+/// and is the name chrono gives the offset otherwise.
+pub(crate) fn render<Tz>(fields: &[Field], offset: Option<i32>, datetime: &DateTime<Tz>) -> String
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    let utc_offset = datetime.offset().fix().local_minus_utc();
+    let mut output = String::new();
+    for field in fields {
+        match field {
+            Field::Literal(literal) => output.push_str(&literal.replace("%%", "%")),
+            Field::MonthName { case, .. }
+            | Field::Weekday { case, .. }
+            | Field::Meridiem { case } => {
+                let word = datetime
+                    .format(&strftime(std::slice::from_ref(field)))
+                    .to_string();
+                output.push_str(&case.apply(&word));
+            }
+            Field::Fraction { digits } => {
+                let nanos = format!("{:09}", datetime.nanosecond() % 1_000_000_000);
+                output.push('.');
+                output.push_str(&nanos[..*digits]);
+            }
+            Field::Zone { name } if offset == Some(utc_offset) => output.push_str(name),
+            Field::Zulu if utc_offset != 0 => {
+                output.push_str(&datetime.format("%:z").to_string());
+            }
+            field => output.push_str(
+                &datetime
+                    .format(&strftime(std::slice::from_ref(field)))
+                    .to_string(),
+            ),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strftime() {
+        let local = NaiveDate::from_ymd_opt(2021, 5, 8)
+            .unwrap()
+            .and_hms_milli_opt(17, 7, 9, 250)
+            .unwrap();
+        let expected = Expected {
+            local,
+            offset: Some(-7 * 3600),
+            has_time: true,
+            dmy: false,
+        };
+        let test_cases = [
+            (
+                "2021-05-08 17:07:09.250 -07:00",
+                Some("%Y-%m-%d %H:%M:%S%.3f %:z"),
+            ),
+            (
+                "Sat, 8 May 2021 05:07:09.250 PM -0700",
+                Some("%a, %-d %b %Y %I:%M:%S%.3f %p %z"),
+            ),
+            ("05/08/21 17:07:09.25 PDT", Some("%m/%d/%y %H:%M:%S%.f %Z")),
+            ("100% 2021-05-08 17:07:09.250", None),
+            ("2021-05-08 17:07:09.250 +07:00", None),
+            ("2021-05-08 noon", None),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            let pattern = fields(input, &expected).map(|fields| super::strftime(&fields));
+            assert_eq!(pattern.as_deref(), want, "strftime/{}", input);
+            if let Some(pattern) = pattern {
+                assert!(matches(input, &pattern, &expected), "matches/{}", input);
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use crate::datetime::{Parse, Reading};
+use anyhow::{anyhow, Result};
+use chrono::prelude::*;
+use jiff::civil;
+use jiff::tz::{self, Disambiguation, TimeZone as JiffTimeZone};
+use jiff::{Timestamp, Zoned};
+
+/// Parses the input into a `jiff::Zoned` in `tz`, with the formats and options of `parse`. Like
+/// [`parse_to_zoned_with`] with jiff's default `Disambiguation::Compatible`: a time skipped by a
+/// DST transition is moved forward by the length of the gap, and a time repeated by one is the
+/// earlier of the two.
+///
+/// ```
+/// use chrono::prelude::*;
+/// use jiff::tz::TimeZone;
+/// use qsv_dateparser::{datetime::Parse, jiff::parse_to_zoned};
+///
+/// let parse = Parse::new(&Utc, NaiveTime::MIN);
+/// let paris = TimeZone::get("Europe/Paris").unwrap();
+///
+/// let zoned = parse_to_zoned(&parse, &paris, "2021-03-28 02:30:00").unwrap();
+/// assert_eq!(zoned.to_string(), "2021-03-28T03:30:00+02:00[Europe/Paris]");
+///
+/// let zoned = parse_to_zoned(&parse, &paris, "2021-05-14 18:51:00 [America/New_York]").unwrap();
+/// assert_eq!(zoned.to_string(), "2021-05-14T18:51:00-04:00[America/New_York]");
+/// ```
+pub fn parse_to_zoned<Tz2>(parse: &Parse<'_, Tz2>, tz: &JiffTimeZone, input: &str) -> Result<Zoned>
+where
+    Tz2: TimeZone,
+{
+    parse_to_zoned_with(parse, tz, Disambiguation::Compatible, input)
+}
+
+/// Parses the input into a `jiff::Zoned`, with the formats and options of `parse` but in the jiff
+/// timezone `tz` instead of the timezone of `parse`.
+///
+/// An input with an offset or a zone abbreviation is that instant in `tz`. An input without one is
+/// a wall-clock time in `tz`, resolved by `disambiguation` when a DST transition skips or repeats
+/// it, and one without a time of day is at the default time of `parse`. A trailing annotation such as `[Europe/Paris]` or `[+02:00]`, as in RFC 9557, takes the
+/// place of `tz` for that input and is kept in the result.
+///
+/// jiff has no leap seconds, so a leap second is read as the last nanosecond of the second before
+/// it, as with [`crate::datetime::LeapSecond::Clamp`].
+pub fn parse_to_zoned_with<Tz2>(
+    parse: &Parse<'_, Tz2>,
+    tz: &JiffTimeZone,
+    disambiguation: Disambiguation,
+    input: &str,
+) -> Result<Zoned>
+where
+    Tz2: TimeZone,
+{
+    let (input, annotated) = match annotation(input) {
+        Some((rest, zone)) => (rest, Some(zone_named(zone)?)),
+        None => (input, None),
+    };
+    let tz = annotated.as_ref().unwrap_or(tz);
+
+    let local = match parse.parse_naive(input)? {
+        Reading::Offset(instant) => {
+            return Timestamp::new(
+                instant.timestamp(),
+                instant.nanosecond().min(999_999_999) as i32,
+            )
+            .map(|timestamp| timestamp.to_zoned(tz.clone()))
+            .map_err(|err| anyhow!("{}: {}", input, err));
+        }
+        Reading::Naive(local) => local,
+        Reading::NaiveDate(date) => date.and_time(parse.default_time()),
+    };
+    civil::DateTime::new(
+        local.year() as i16,
+        local.month() as i8,
+        local.day() as i8,
+        local.hour() as i8,
+        local.minute() as i8,
+        local.second() as i8,
+        local.nanosecond().min(999_999_999) as i32,
+    )
+    .and_then(|local| tz.to_ambiguous_zoned(local).disambiguate(disambiguation))
+    .map_err(|err| anyhow!("{}: {}", input, err))
+}
+
+// Splits a trailing `[zone]` annotation off the input, without the `!` that marks it critical.
+fn annotation(input: &str) -> Option<(&str, &str)> {
+    let (rest, zone) = input.strip_suffix(']')?.rsplit_once('[')?;
+    Some((rest.trim_end(), zone.strip_prefix('!').unwrap_or(zone)))
+}
+
+fn zone_named(zone: &str) -> Result<JiffTimeZone> {
+    if zone.starts_with(['+', '-']) {
+        let offset = crate::timezone::parse(zone)?;
+        return tz::Offset::from_seconds(offset.local_minus_utc())
+            .map(JiffTimeZone::fixed)
+            .map_err(|err| anyhow!("{}: {}", zone, err));
+    }
+    JiffTimeZone::get(zone).map_err(|_| anyhow!("{} is not a known timezone.", zone))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_to_zoned() {
+        let parse = Parse::new(&Utc, NaiveTime::MIN);
+        let new_york = JiffTimeZone::get("America/New_York").unwrap();
+        let test_cases = [
+            (
+                "2021-04-30 21:14:10",
+                "2021-04-30T21:14:10-04:00[America/New_York]",
+            ),
+            (
+                "2021-05-14 18:51 PDT",
+                "2021-05-14T21:51:00-04:00[America/New_York]",
+            ),
+            ("1620021848", "2021-05-03T02:04:08-04:00[America/New_York]"),
+            (
+                "May 8, 2009 5:57:51 PM",
+                "2009-05-08T17:57:51-04:00[America/New_York]",
+            ),
+            // skipped by the spring DST transition
+            (
+                "2021-03-14 02:30:00",
+                "2021-03-14T03:30:00-04:00[America/New_York]",
+            ),
+            // the end of the day, without an offset
+            (
+                "2021-05-14 24:00",
+                "2021-05-15T00:00:00-04:00[America/New_York]",
+            ),
+            (
+                "2021-05-14 24:00:00 PDT",
+                "2021-05-15T03:00:00-04:00[America/New_York]",
+            ),
+            // repeated by the fall DST transition
+            (
+                "2021-11-07 01:30:00",
+                "2021-11-07T01:30:00-04:00[America/New_York]",
+            ),
+            (
+                "2021-06-14T10:00:00+02:00[Europe/Paris]",
+                "2021-06-14T10:00:00+02:00[Europe/Paris]",
+            ),
+            (
+                "2021-06-14 10:00:00 [!Europe/Paris]",
+                "2021-06-14T10:00:00+02:00[Europe/Paris]",
+            ),
+            (
+                "2021-06-14 10:00:00[+05:30]",
+                "2021-06-14T10:00:00+05:30[+05:30]",
+            ),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                super::parse_to_zoned(&parse, &new_york, input)
+                    .unwrap()
+                    .to_string(),
+                want,
+                "parse_to_zoned/{}",
+                input
+            );
+        }
+
+        let later = super::parse_to_zoned_with(
+            &parse,
+            &new_york,
+            Disambiguation::Later,
+            "2021-11-07 01:30:00",
+        )
+        .unwrap();
+        assert_eq!(
+            later.to_string(),
+            "2021-11-07T01:30:00-05:00[America/New_York]"
+        );
+        assert!(super::parse_to_zoned_with(
+            &parse,
+            &new_york,
+            Disambiguation::Reject,
+            "2021-03-14 02:30:00"
+        )
+        .is_err());
+
+        assert!(super::parse_to_zoned(&parse, &new_york, "not-a-date").is_err());
+        assert_eq!(
+            super::parse_to_zoned(&parse, &new_york, "2021-06-14 10:00 [Mars/Olympus]")
+                .unwrap_err()
+                .to_string(),
+            "Mars/Olympus is not a known timezone."
+        );
+    }
+}
@@ -0,0 +1,878 @@
+use chrono::{NaiveTime, Weekday};
+use std::ops::RangeInclusive;
+
+/// The leading tokens of an input, which are enough to tell which families might match it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Shape {
+    /// `1671673426.123`, `-770172300`, `2021`
+    Numeric,
+    /// `2021-05-14...`
+    YearDash,
+    /// `2021-Feb-21`, `2021-Q2`
+    YearDashWord,
+    /// `2021/05/14...`
+    YearSlash,
+    /// `05/14/2021...`, `05/2021`
+    DaySlash,
+    /// `14/May/2021:...`
+    DaySlashWord,
+    /// `[14/May/2021:...]`
+    Bracket,
+    /// `14 May 2021...`, `02 Jun 2021 06:31:39 GMT`
+    DayWord,
+    /// `Wed, 02 Jun 2021...`, `Sunday, 06-Nov-94...`
+    WordComma,
+    /// `May 14, 2021...`, `May 2021`, `Sept. 14`
+    WordDigits,
+    /// `Wed Jun  2 06:31:39 2021`
+    WordWord,
+    /// Anything else, which has to go through every family.
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Digits,
+    Alpha,
+    Space,
+    Punct(u8),
+}
+
+/// A run of digits, letters or whitespace, or a single other byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Token {
+    kind: Kind,
+    start: usize,
+    end: usize,
+}
+
+impl Token {
+    const EMPTY: Token = Token {
+        kind: Kind::Space,
+        start: 0,
+        end: 0,
+    };
+
+    #[inline]
+    const fn len(self) -> usize {
+        self.end - self.start
+    }
+}
+
+// enough for the longest spelling the families read, such as
+// `September 17, 2012 at 10:09:00.123 p.m. PDT`
+const MAX_TOKENS: usize = 32;
+
+/// The tokens of an input, and what a single pass over it found out about it.
+#[derive(Clone, Debug)]
+pub(crate) struct Lexed<'a> {
+    input: &'a [u8],
+    tokens: [Token; MAX_TOKENS],
+    len: usize,
+    // whether all of the input fit in `tokens`; an input that didn't matches no family
+    complete: bool,
+    pub(crate) shape: Shape,
+    /// The input has a "noon" or "midnight" word, which the zone-aware families would otherwise
+    /// read as an unknown timezone.
+    pub(crate) time_word: bool,
+    /// The input has something that only the other spellings of the time of day use, such as
+    /// `1430`, `14h30`, `14.30`, `p.m.` or `at`. Inputs without one can fail fast.
+    pub(crate) time_hint: bool,
+    /// Read the time of day in the other spellings rather than as `hh:mm[:ss]`.
+    pub(crate) spelled: bool,
+}
+
+impl<'a> Lexed<'a> {
+    /// A cursor at the first token.
+    #[inline]
+    pub(crate) const fn cursor(&self) -> Cursor<'_, 'a> {
+        Cursor { lexed: self, at: 0 }
+    }
+
+    /// The input the tokens were read from.
+    #[inline]
+    pub(crate) const fn input(&self) -> &'a [u8] {
+        self.input
+    }
+
+    /// The step of a `24:00` time of day, in seconds: 1 for `24:00:00`, 60 for `24:00`, and 0
+    /// when there is none.
+    pub(crate) fn end_of_day(&self) -> i64 {
+        let tokens = self.tokens();
+        for at in 0..tokens.len() {
+            // after the date, as in 2021-05-14 24:00, 2021-05-14T24:00 or 14/May/2021:24:00
+            let after_date = match at.checked_sub(1).map(|before| tokens[before]) {
+                None => true,
+                Some(token) => match token.kind {
+                    Kind::Space | Kind::Punct(b',') | Kind::Alpha => true,
+                    Kind::Punct(b':') => at >= 2 && tokens[at - 2].len() == 4,
+                    _ => false,
+                },
+            };
+            let mut time = Cursor { lexed: self, at };
+            if after_date
+                && time.digits(2..=2) == Some(b"24")
+                && time.punct(b':').is_some()
+                && time.digits(2..=2) == Some(b"00")
+            {
+                let seconds = time.punct(b':').is_some() && time.number(2..=2) == Some(0);
+                return if seconds { 1 } else { 60 };
+            }
+        }
+        0
+    }
+
+    #[inline]
+    fn tokens(&self) -> &[Token] {
+        &self.tokens[..self.len]
+    }
+
+    #[inline]
+    fn text(&self, token: Token) -> &'a [u8] {
+        &self.input[token.start..token.end]
+    }
+}
+
+/// Splits the input into runs of digits, letters and whitespace, and classifies it by its first
+/// few tokens. Doesn't allocate.
+pub(crate) fn lex(input: &[u8]) -> Lexed<'_> {
+    let mut lexed = Lexed {
+        input,
+        tokens: [Token::EMPTY; MAX_TOKENS],
+        len: 0,
+        complete: true,
+        shape: Shape::Other,
+        time_word: false,
+        time_hint: false,
+        spelled: false,
+    };
+    let mut time_word = false;
+    let mut time_hint = false;
+    let mut previous = [None; 2];
+    let mut ascii = true;
+    let mut numeric = !input.is_empty();
+    let mut dots = 0;
+
+    let mut i = 0;
+    while i < input.len() {
+        let c = input[i];
+        let start = i;
+        let kind = if c.is_ascii_digit() {
+            while i < input.len() && input[i].is_ascii_digit() {
+                i += 1;
+            }
+            Kind::Digits
+        } else if c.is_ascii_alphabetic() {
+            while i < input.len() && input[i].is_ascii_alphabetic() {
+                i += 1;
+            }
+            let word = &input[start..i];
+            time_word |=
+                word.eq_ignore_ascii_case(b"noon") || word.eq_ignore_ascii_case(b"midnight");
+            time_hint |= [&b"am"[..], b"pm", b"a", b"p", b"at"]
+                .iter()
+                .any(|hint| word.eq_ignore_ascii_case(hint))
+                || (word.eq_ignore_ascii_case(b"h")
+                    && matches!(previous[1], Some((Kind::Digits, _))));
+            Kind::Alpha
+        } else if c.is_ascii_whitespace() {
+            while i < input.len() && input[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            Kind::Space
+        } else {
+            ascii &= c.is_ascii();
+            i += 1;
+            Kind::Punct(c)
+        };
+        let token = (kind, i - start);
+
+        time_hint |= matches!(
+            (previous, token),
+            ([_, Some((Kind::Space, _))], (Kind::Digits, 4))
+                | (
+                    [Some((Kind::Space, _)), Some((Kind::Digits, 1..=2))],
+                    (Kind::Punct(b'.'), _)
+                )
+        );
+        previous = [previous[1], Some(token)];
+
+        numeric &= match kind {
+            Kind::Digits => true,
+            Kind::Punct(b'.') => {
+                dots += 1;
+                dots == 1
+            }
+            Kind::Punct(b'+' | b'-') => start == 0,
+            _ => false,
+        };
+        if lexed.len < MAX_TOKENS {
+            lexed.tokens[lexed.len] = Token {
+                kind,
+                start,
+                end: i,
+            };
+            lexed.len += 1;
+        } else {
+            lexed.complete = false;
+        }
+    }
+
+    lexed.shape = if !ascii {
+        Shape::Other
+    } else if numeric && input.iter().any(u8::is_ascii_digit) {
+        Shape::Numeric
+    } else {
+        classify(lexed.tokens())
+    };
+    lexed.time_word = time_word;
+    lexed.time_hint = time_hint || time_word;
+    lexed
+}
+
+#[inline]
+fn classify(tokens: &[Token]) -> Shape {
+    use Kind::*;
+
+    let mut leading = [None; 4];
+    for (lead, token) in leading.iter_mut().zip(tokens) {
+        *lead = Some((token.kind, token.len()));
+    }
+    match leading {
+        [Some((Digits, 4)), Some((Punct(b'-'), _)), Some((Digits, _)), _] => Shape::YearDash,
+        [Some((Digits, 4)), Some((Punct(b'-'), _)), Some((Alpha, _)), _] => Shape::YearDashWord,
+        [Some((Digits, 4)), Some((Punct(b'/'), _)), Some((Digits, _)), _] => Shape::YearSlash,
+        [Some((Digits, 1..=2)), Some((Punct(b'/'), _)), Some((Digits, _)), _] => Shape::DaySlash,
+        [Some((Digits, 1..=2)), Some((Punct(b'/'), _)), Some((Alpha, _)), _] => Shape::DaySlashWord,
+        [Some((Punct(b'['), _)), ..] => Shape::Bracket,
+        [Some((Digits, 1..=2)), Some((Space, _)), Some((Alpha, _)), _] => Shape::DayWord,
+        [Some((Alpha, _)), Some((Punct(b','), _)), ..] => Shape::WordComma,
+        [Some((Alpha, _)), Some((Space, _)), Some((Digits, _)), _]
+        | [Some((Alpha, _)), Some((Punct(b'.'), _)), Some((Space, _)), Some((Digits, _))] => {
+            Shape::WordDigits
+        }
+        [Some((Alpha, _)), Some((Space, _)), Some((Alpha, _)), _] => Shape::WordWord,
+        _ => Shape::Other,
+    }
+}
+
+/// How a family writes the time of day after its date.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Layout {
+    pub(crate) separator: Separator,
+    /// The hour has two digits.
+    pub(crate) padded: bool,
+    /// The seconds are always there.
+    pub(crate) seconds: bool,
+    /// The seconds can have a fraction.
+    pub(crate) fraction: bool,
+    /// A 12-hour time with `am` or `pm` is accepted too.
+    pub(crate) meridiem: bool,
+}
+
+/// What comes between the date and the time of day.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Separator {
+    /// `2021-04-30 21:14`
+    Space,
+    /// `12 Feb 2006, 19:17`
+    Comma,
+    /// `May 26, 2021, 12:49 AM PDT`, `September 17, 2012 at 10:09am PST`
+    CommaAt,
+    /// `14/May/2021:18:51:00 -0700`
+    Colon,
+    /// `2021-05-01T01:17:02Z`
+    T,
+}
+
+/// A time of day, with the number of seconds to move it forward by when it was written as
+/// `24:00`: chrono only has hours up to 23, so that is the last second (or minute) of the day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Clock {
+    pub(crate) time: NaiveTime,
+    pub(crate) step: i64,
+}
+
+impl Clock {
+    // A second of 60 is a leap second.
+    #[inline]
+    fn new(hour: u32, minute: u32, second: Option<u32>, nano: u32) -> Option<Self> {
+        let (time, step) = match (hour, minute, second, nano) {
+            (24, 0, None, 0) => (NaiveTime::from_hms_opt(23, 59, 0)?, 60),
+            (24, 0, Some(0), 0) => (NaiveTime::from_hms_opt(23, 59, 59)?, 1),
+            (_, _, Some(60), _) => (
+                NaiveTime::from_hms_nano_opt(hour, minute, 59, nano + 1_000_000_000)?,
+                0,
+            ),
+            _ => (
+                NaiveTime::from_hms_nano_opt(hour, minute, second.unwrap_or(0), nano)?,
+                0,
+            ),
+        };
+        Some(Self { time, step })
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("monday", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+];
+
+/// A position in the tokens of an input. Copies of it are checkpoints to go back to.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Cursor<'l, 'a> {
+    lexed: &'l Lexed<'a>,
+    at: usize,
+}
+
+impl<'l, 'a> Cursor<'l, 'a> {
+    #[inline]
+    fn take(&mut self, want: impl FnOnce(Token) -> bool) -> Option<&'a [u8]> {
+        let token = *self
+            .lexed
+            .tokens()
+            .get(self.at)
+            .filter(|&&token| want(token))?;
+        self.at += 1;
+        Some(self.lexed.text(token))
+    }
+
+    /// Succeeds when all of the input has been read.
+    #[inline]
+    pub(crate) fn end(&self) -> Option<()> {
+        (self.at == self.lexed.len && self.lexed.complete).then_some(())
+    }
+
+    /// The rest of the input.
+    #[inline]
+    pub(crate) fn rest(&self) -> &'a [u8] {
+        match self.lexed.tokens().get(self.at) {
+            Some(token) => &self.lexed.input[token.start..],
+            None => &[],
+        }
+    }
+
+    /// A run of digits, with as many of them as `width` allows.
+    #[inline]
+    pub(crate) fn digits(&mut self, width: RangeInclusive<usize>) -> Option<&'a [u8]> {
+        self.take(|token| token.kind == Kind::Digits && width.contains(&token.len()))
+    }
+
+    /// The value of a run of at most 9 digits.
+    #[inline]
+    pub(crate) fn number(&mut self, width: RangeInclusive<usize>) -> Option<u32> {
+        self.digits(width).map(value)
+    }
+
+    /// A year of 2 to 4 digits, with whether it has two.
+    #[inline]
+    pub(crate) fn year(&mut self) -> Option<(i32, bool)> {
+        self.digits(2..=4)
+            .map(|digits| (value(digits) as i32, digits.len() == 2))
+    }
+
+    /// A run of letters.
+    #[inline]
+    pub(crate) fn word(&mut self) -> Option<&'a [u8]> {
+        self.take(|token| token.kind == Kind::Alpha)
+    }
+
+    /// The given word, in any case.
+    #[inline]
+    pub(crate) fn keyword(&mut self, keyword: &str) -> Option<()> {
+        let lexed = self.lexed;
+        self.take(|token| {
+            token.kind == Kind::Alpha && lexed.text(token).eq_ignore_ascii_case(keyword.as_bytes())
+        })
+        .map(drop)
+    }
+
+    /// The given punctuation byte.
+    #[inline]
+    pub(crate) fn punct(&mut self, c: u8) -> Option<()> {
+        self.take(|token| token.kind == Kind::Punct(c)).map(drop)
+    }
+
+    /// A run of whitespace.
+    #[inline]
+    pub(crate) fn space(&mut self) -> Option<()> {
+        self.take(|token| token.kind == Kind::Space).map(drop)
+    }
+
+    /// A hyphen or an en dash.
+    #[inline]
+    pub(crate) fn dash(&mut self) -> Option<()> {
+        let mut cursor = *self;
+        if cursor.punct(b'-').is_none() && !"–".bytes().all(|c| cursor.punct(c).is_some()) {
+            return None;
+        }
+        *self = cursor;
+        Some(())
+    }
+
+    /// The name of a month, a three-letter abbreviation or `Sept`, in any case. `width` is the
+    /// number of letters it may have.
+    #[inline]
+    pub(crate) fn month(&mut self, width: RangeInclusive<usize>) -> Option<u32> {
+        let mut cursor = *self;
+        let word = cursor.word().filter(|word| width.contains(&word.len()))?;
+        let month = if word.eq_ignore_ascii_case(b"sept") {
+            9
+        } else {
+            MONTHS.iter().position(|name| named(word, name))? as u32 + 1
+        };
+        *self = cursor;
+        Some(month)
+    }
+
+    /// The name of a weekday or its three-letter abbreviation, in any case. `width` is the number
+    /// of letters it may have.
+    #[inline]
+    pub(crate) fn weekday(&mut self, width: RangeInclusive<usize>) -> Option<Weekday> {
+        let mut cursor = *self;
+        let word = cursor.word().filter(|word| width.contains(&word.len()))?;
+        let (_, weekday) = WEEKDAYS.iter().find(|(name, _)| named(word, name))?;
+        *self = cursor;
+        Some(*weekday)
+    }
+
+    /// A timezone abbreviation or offset, such as `PDT`, `+0800` or `+09:00`, of as many bytes
+    /// as `width` allows, for [`crate::timezone::parse`] to read.
+    #[inline]
+    pub(crate) fn zone(&mut self, width: RangeInclusive<usize>) -> Option<&'a [u8]> {
+        let mut cursor = *self;
+        let start = cursor.rest();
+        while cursor
+            .take(|token| {
+                matches!(
+                    token.kind,
+                    Kind::Alpha | Kind::Digits | Kind::Punct(b'+' | b'-' | b':')
+                )
+            })
+            .is_some()
+        {}
+        let zone = &start[..start.len() - cursor.rest().len()];
+        if !width.contains(&zone.len()) {
+            return None;
+        }
+        *self = cursor;
+        Some(zone)
+    }
+
+    /// A comment in parentheses, which can nest and has `\\` escapes, as RFC 2822 allows after
+    /// the zone.
+    #[inline]
+    pub(crate) fn comment(&mut self) -> Option<()> {
+        let mut cursor = *self;
+        cursor.punct(b'(')?;
+        let mut depth = 1;
+        while depth > 0 {
+            let token = *cursor.lexed.tokens().get(cursor.at)?;
+            cursor.at += 1;
+            match token.kind {
+                Kind::Punct(b'(') => depth += 1,
+                Kind::Punct(b')') => depth -= 1,
+                Kind::Punct(b'\\') => cursor.at += 1,
+                _ => {}
+            }
+        }
+        *self = cursor;
+        Some(())
+    }
+
+    /// The time of day after a date, with what comes between them. An input that spells the time
+    /// another way is read as in [`Cursor::spelled_clock`].
+    #[inline]
+    pub(crate) fn clock(&mut self, layout: Layout) -> Option<Clock> {
+        if self.lexed.spelled {
+            return self.spelled_clock(layout);
+        }
+        match layout.separator {
+            Separator::Space => self.space()?,
+            Separator::Comma => {
+                self.punct(b',');
+                self.space()?
+            }
+            Separator::CommaAt => {
+                let mut cursor = *self;
+                cursor.space();
+                let comma = cursor.punct(b',').is_some();
+                if comma | cursor.keyword("at").is_some() {
+                    *self = cursor;
+                }
+                self.space()?
+            }
+            Separator::Colon => self.punct(b':')?,
+            Separator::T => {
+                let lexed = self.lexed;
+                self.keyword("t").or_else(|| {
+                    self.take(|token| token.kind == Kind::Space && lexed.text(token) == b" ")
+                        .map(drop)
+                })?
+            }
+        }
+
+        let hour = self.number(if layout.padded { 2..=2 } else { 1..=2 })?;
+        self.punct(b':')?;
+        let minute = self.number(2..=2)?;
+        let second = match self.punct(b':') {
+            Some(()) => Some(self.number(2..=2)?),
+            None if layout.seconds => return None,
+            None => None,
+        };
+        let mut cursor = *self;
+        let nano = match (second, cursor.punct(b'.')) {
+            (Some(_), Some(())) if layout.fraction => {
+                *self = cursor;
+                Some(fraction(self.digits(1..=9)?))
+            }
+            _ => None,
+        };
+
+        let mut cursor = *self;
+        cursor.space();
+        let pm = match cursor.word() {
+            Some(word) if word.eq_ignore_ascii_case(b"pm") => Some(true),
+            Some(word) if word.eq_ignore_ascii_case(b"am") => Some(false),
+            _ => None,
+        };
+        let hour = match pm {
+            Some(pm) if layout.meridiem && nano.is_none() => {
+                *self = cursor;
+                twelve_hour(hour, pm)?
+            }
+            _ => hour,
+        };
+        Clock::new(hour, minute, second, nano.unwrap_or(0))
+    }
+
+    // the other spellings of the time of day, after a date
+    // - 2021-05-14 1430
+    // - 2021-05-14 14h30
+    // - 05/14/2021 14.30
+    // - May 14, 2021 at 2:30 p.m.
+    // - 14 May 2021 2 Pm
+    // - 2021-05-14 noon
+    // - 2021-05-14 12 midnight PDT
+    //
+    // A date that runs into its time with a colon, as in the common log format, has none of these.
+    #[inline]
+    fn spelled_clock(&mut self, layout: Layout) -> Option<Clock> {
+        if let Separator::Colon = layout.separator {
+            return None;
+        }
+        let lexed = self.lexed;
+        let date = &lexed.tokens()[..self.at];
+        self.punct(b',');
+        self.space()?;
+        let mut cursor = *self;
+        if cursor.keyword("at").and_then(|_| cursor.space()).is_some() {
+            *self = cursor;
+        }
+
+        let mut cursor = *self;
+        if cursor.digits(2..=2) != Some(b"12") || cursor.space().is_none() {
+            cursor = *self;
+        }
+        let word = if cursor.keyword("noon").is_some() {
+            Some(12)
+        } else if cursor.keyword("midnight").is_some() {
+            Some(0)
+        } else {
+            None
+        };
+        if let Some(hour) = word {
+            *self = cursor;
+            return Clock::new(hour, 0, None, 0).filter(|_| !layout.seconds);
+        }
+
+        // four digits after a date are its year unless it already has one, and a repeated
+        // year is not a time either
+        if let Some(military) = self.digits(4..=4) {
+            let mut years = date
+                .iter()
+                .filter(|token| token.kind == Kind::Digits && token.len() == 4)
+                .map(|&token| lexed.text(token))
+                .peekable();
+            if years.peek().is_none() || years.any(|year| year == military) {
+                return None;
+            }
+            let (hour, minute) = (value(&military[..2]), value(&military[2..]));
+            if hour > 23 || minute > 59 || layout.seconds {
+                return None;
+            }
+            return Clock::new(hour, minute, None, 0);
+        }
+
+        let hour = self.number(1..=2)?;
+        let mut marked = false;
+        let (minute, second, nano) = if self.keyword("h").is_some() {
+            marked = true;
+            (self.number(2..=2), None, None)
+        } else if self.punct(b'.').is_some() {
+            (Some(self.number(2..=2)?), None, None)
+        } else if self.punct(b':').is_some() {
+            let minute = self.number(2..=2)?;
+            let second = match self.punct(b':') {
+                Some(()) => Some(self.number(2..=2)?),
+                None => None,
+            };
+            let mut cursor = *self;
+            let nano = match (second, cursor.punct(b'.')) {
+                (Some(_), Some(())) => {
+                    *self = cursor;
+                    Some(fraction(self.digits(1..=9)?))
+                }
+                _ => None,
+            };
+            (Some(minute), second, nano)
+        } else {
+            (None, None, None)
+        };
+        if (layout.seconds && second.is_none()) || (!layout.fraction && nano.is_some()) {
+            return None;
+        }
+
+        let mut cursor = *self;
+        cursor.space();
+        let hour = match cursor.meridiem() {
+            Some(pm) => {
+                *self = cursor;
+                twelve_hour(hour, pm)?
+            }
+            // a bare hour needs a meridiem, as in "2 pm"
+            None if minute.is_none() && !marked => return None,
+            // the end of the day is only written with colons
+            None if hour > 23 => return None,
+            None => {
+                self.trailing_space();
+                hour
+            }
+        };
+        Clock::new(hour, minute.unwrap_or(0), second, nano.unwrap_or(0))
+    }
+
+    // whitespace after a time without a meridiem, when nothing follows it
+    #[inline]
+    fn trailing_space(&mut self) {
+        let mut cursor = *self;
+        if cursor.space().and_then(|_| cursor.end()).is_some() {
+            *self = cursor;
+        }
+    }
+
+    // am or pm, also written a.m., a m or a: true for pm
+    #[inline]
+    fn meridiem(&mut self) -> Option<bool> {
+        let word = self.word()?;
+        let pm = if word.eq_ignore_ascii_case(b"pm") || word.eq_ignore_ascii_case(b"p") {
+            true
+        } else if word.eq_ignore_ascii_case(b"am") || word.eq_ignore_ascii_case(b"a") {
+            false
+        } else {
+            return None;
+        };
+        if word.len() == 1 {
+            let mut cursor = *self;
+            cursor.punct(b'.');
+            cursor.space();
+            if cursor.keyword("m").is_some() {
+                *self = cursor;
+            } else {
+                self.punct(b'.');
+                return Some(pm);
+            }
+        }
+        self.punct(b'.');
+        Some(pm)
+    }
+}
+
+// A word is a name if it is the name or its first three letters, in any case.
+#[inline]
+fn named(word: &[u8], name: &str) -> bool {
+    (word.len() == 3 || word.len() == name.len())
+        && word.eq_ignore_ascii_case(&name.as_bytes()[..word.len().min(name.len())])
+}
+
+// An hour of a 12-hour clock, which goes from 1 to 12, on the 24-hour clock.
+#[inline]
+fn twelve_hour(hour: u32, pm: bool) -> Option<u32> {
+    (1..=12)
+        .contains(&hour)
+        .then(|| hour % 12 + if pm { 12 } else { 0 })
+}
+
+// The nanoseconds of a fraction of a second of at most 9 digits.
+#[inline]
+fn fraction(digits: &[u8]) -> u32 {
+    value(digits) * 10_u32.pow(9 - digits.len() as u32)
+}
+
+/// The value of a run of at most 9 digits.
+#[inline]
+pub(crate) fn value(digits: &[u8]) -> u32 {
+    digits
+        .iter()
+        .fold(0, |value, &c| value * 10 + u32::from(c - b'0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lex() {
+        let test_cases = [
+            ("1671673426.123", Shape::Numeric),
+            ("-770172300", Shape::Numeric),
+            ("2021", Shape::Numeric),
+            ("2017-11-25T22:34:50Z", Shape::YearDash),
+            ("2021-Feb-21", Shape::YearDashWord),
+            ("2012/03/19 10:11:59", Shape::YearSlash),
+            ("03/19/2012 10:11:59", Shape::DaySlash),
+            ("14/May/2021:18:51:00 +0000", Shape::DaySlashWord),
+            ("[14/May/2021:18:51:00 -0700]", Shape::Bracket),
+            ("14 May 2019 19:11:40.164", Shape::DayWord),
+            ("Wed, 02 Jun 2021 06:31:39 GMT", Shape::WordComma),
+            ("May 8, 2009 5:57:51 PM", Shape::WordDigits),
+            ("oct. 7, 1970", Shape::WordDigits),
+            ("Wed Jun  2 06:31:39 2021", Shape::WordWord),
+            ("171113 14:14:20", Shape::Other),
+            ("Q2 2021", Shape::Other),
+            ("1.2.3", Shape::Other),
+            ("", Shape::Other),
+            ("14 mai 2021 à midi", Shape::Other),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(super::lex(input.as_bytes()).shape, want, "lex/{}", input)
+        }
+        assert!(super::lex(b"2021-05-14 12 Midnight PDT").time_word);
+        assert!(!super::lex(b"2021-05-14 12:00 PDT").time_word);
+
+        for input in [
+            "2021-05-14 1430",
+            "2021-05-14 14h30",
+            "05/14/2021 14.30",
+            "May 14, 2021 2:30 p.m.",
+            "2021-05-14 2pm",
+            "May 14, 2021 at 2:30",
+        ] {
+            assert!(super::lex(input.as_bytes()).time_hint, "lex/{}", input)
+        }
+        for input in ["2019-11-29 08:08:05-08", "2014.03.30", "171113 14:14:20"] {
+            assert!(!super::lex(input.as_bytes()).time_hint, "lex/{}", input)
+        }
+
+        let long = "1 ".repeat(40);
+        let lexed = super::lex(long.as_bytes());
+        let mut cursor = lexed.cursor();
+        while cursor.number(1..=1).and_then(|_| cursor.space()).is_some() {}
+        assert!(cursor.end().is_none());
+    }
+
+    #[test]
+    fn clock() {
+        let layout = Layout {
+            separator: Separator::Space,
+            padded: false,
+            seconds: false,
+            fraction: true,
+            meridiem: true,
+        };
+        let time = |h, m, s, nano| NaiveTime::from_hms_nano_opt(h, m, s, nano);
+        let test_cases = [
+            (" 21:14", time(21, 14, 0, 0), 0),
+            (" 21:14:10", time(21, 14, 10, 0), 0),
+            ("  21:14:10.052282", time(21, 14, 10, 52_282_000), 0),
+            (" 5:24:37 PM", time(17, 24, 37, 0), 0),
+            (" 12:00am", time(0, 0, 0, 0), 0),
+            (" 23:59:60", time(23, 59, 59, 1_000_000_000), 0),
+            (" 24:00", time(23, 59, 0, 0), 60),
+            (" 24:00:00.000", time(23, 59, 59, 0), 1),
+            (" 24:00:01", None, 0),
+            (" 13:00 pm", None, 0),
+            (" 10:11:59.3 PM", None, 0),
+            (" 21:14.5", None, 0),
+        ];
+
+        for &(input, want, step) in test_cases.iter() {
+            let lexed = super::lex(input.as_bytes());
+            let mut cursor = lexed.cursor();
+            let clock = cursor.clock(layout).filter(|_| cursor.end().is_some());
+            assert_eq!(
+                clock,
+                want.map(|time| Clock { time, step }),
+                "clock/{}",
+                input
+            )
+        }
+
+        let test_cases = [
+            (", at 2:30 p.m.", time(14, 30, 0, 0)),
+            (" 14h", time(14, 0, 0, 0)),
+            (" 9 A M", time(9, 0, 0, 0)),
+            (" 12 noon", time(12, 0, 0, 0)),
+            (" midnight", time(0, 0, 0, 0)),
+            (" 14", None),
+            (" 14h75", None),
+            (" 0 pm", None),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            let mut lexed = super::lex(input.as_bytes());
+            lexed.spelled = true;
+            let mut cursor = lexed.cursor();
+            let clock = cursor.clock(layout).filter(|_| cursor.end().is_some());
+            assert_eq!(
+                clock.map(|clock| clock.time),
+                want,
+                "spelled_clock/{}",
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn names() {
+        let lexed = super::lex(b"may SEPT Sept. september Sep Septem Tue tuesday tues");
+        let mut cursor = lexed.cursor();
+        let mut months = vec![];
+        while let Some(month) = cursor.month(3..=9) {
+            months.push(month);
+            cursor.punct(b'.');
+            cursor.space();
+        }
+        assert_eq!(months, [5, 9, 9, 9, 9]);
+        assert_eq!(cursor.month(3..=9), None);
+        cursor.word();
+        cursor.space();
+        assert_eq!(cursor.weekday(3..=3), Some(Weekday::Tue));
+        cursor.space();
+        assert_eq!(cursor.weekday(3..=3), None);
+        assert_eq!(cursor.weekday(6..=9), Some(Weekday::Tue));
+        cursor.space();
+        assert_eq!(cursor.weekday(3..=9), None);
+    }
+}
@@ -38,6 +38,8 @@
 //!     "2017-11-25T22:34:50Z",
 //!     // rfc2822
 //!     "Wed, 02 Jun 2021 06:31:39 GMT",
+//!     // rfc850
+//!     "Sunday, 06-Nov-94 08:49:37 GMT",
 //!     // yyyy-mm-dd hh:mm:ss
 //!     "2014-04-26 05:24:37 PM",
 //!     "2021-04-30 21:14",
@@ -55,6 +57,14 @@
 //!     "2014-04-26 13:13:44 +09:00",
 //!     "2012-08-03 18:31:59.257000000 +0000",
 //!     "2015-09-30 18:48:56.35272715 UTC",
+//!     // end of day and leap seconds
+//!     "2021-05-14 24:00:00",
+//!     "2016-12-31 23:59:60",
+//!     // military, compact and word times of day
+//!     "2021-05-14 1430",
+//!     "2021-05-14 14h30",
+//!     "May 14, 2021 2:30 p.m.",
+//!     "2021-05-14 12 noon",
 //!     // yyyy-mm-dd
 //!     "2021-02-21",
 //!     // yyyy-mm-dd z
@@ -116,6 +126,28 @@
 //!     // yyyy/mm/dd
 //!     "2014/3/31",
 //!     "2014/03/31",
+//!     // Mon dd hh:mm:ss (syslog, year inferred)
+//!     "May 27 02:45:27",
+//!     "Jun  2 06:31:39",
+//!     // Day Mon dd hh:mm:ss yyyy (asctime)
+//!     "Wed Jun  2 06:31:39 2021",
+//!     // Day Mon dd hh:mm:ss z yyyy (Unix `date`)
+//!     "Wed Jun  2 06:31:39 PDT 2021",
+//!     // [dd/Mon/yyyy:hh:mm:ss z] (Common Log Format)
+//!     "[14/May/2021:18:51:00 -0700]",
+//!     "14/May/2021:18:51:00 +0000",
+//!     // partial dates (filled to the start of the period by default)
+//!     "2021",
+//!     "2021-05",
+//!     "May 2021",
+//!     "05/2021",
+//!     "May 14",
+//!     // quarters, half years and fiscal years
+//!     "2021Q2",
+//!     "Q2 2021",
+//!     "2021-Q2",
+//!     "H1 2021",
+//!     "FY2022 Q1",
 //! ];
 //!
 //! for date_str in accepted {
@@ -154,6 +186,26 @@
 //!     assert!(result.is_ok());
 //! }
 //! ```
+//!
+//! ### Two-digit years
+//!
+//! Two-digit years are expanded into 1969-2068 by default. Use
+//! [`datetime::Parse::two_digit_year`] to set a different pivot, a window that slides with the
+//! current year, or to reject them.
+//!
+//! ```
+//! use chrono::prelude::*;
+//! use qsv_dateparser::datetime::{Parse, TwoDigitYear};
+//!
+//! let mut parse = Parse::new(&Utc, NaiveTime::MIN);
+//! assert_eq!(parse.parse("06/27/68").unwrap().year(), 2068);
+//!
+//! parse.two_digit_year(TwoDigitYear::Pivot(1950));
+//! assert_eq!(parse.parse("06/27/68").unwrap().year(), 1968);
+//!
+//! parse.two_digit_year(TwoDigitYear::Reject);
+//! assert!(parse.parse("06/27/68").is_err());
+//! ```
 
 /// Datetime string parser
 ///
@@ -201,6 +253,136 @@ pub mod datetime;
 /// ```
 pub mod timezone;
 
+/// Duration and elapsed time string parser
+///
+/// ```
+/// use chrono::Duration;
+/// use qsv_dateparser::duration::parse;
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     assert_eq!(parse("PT1H30M")?, Duration::minutes(90));
+///     assert_eq!(parse("P3DT4H")?, Duration::hours(76));
+///     assert_eq!(parse("01:30:00")?, Duration::minutes(90));
+///     assert_eq!(parse("1h 30m")?, Duration::minutes(90));
+///     assert_eq!(parse("90 min")?, Duration::minutes(90));
+///
+///     Ok(())
+/// }
+/// ```
+pub mod duration;
+
+/// Apache Arrow string column to timestamp column conversion, behind the `arrow` feature
+///
+/// ```
+/// use arrow_array::{Array, StringArray};
+/// use arrow_schema::TimeUnit;
+/// use chrono::prelude::*;
+/// use qsv_dateparser::{arrow::to_timestamp, datetime::Parse};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let parse = Parse::new(&Utc, NaiveTime::MIN);
+///     let column = StringArray::from(vec![Some("2021-05-14 18:51 PDT"), None, Some("n/a")]);
+///
+///     let parsed = to_timestamp(&parse, &column, TimeUnit::Millisecond, Some("UTC".into()), false)?;
+///     assert_eq!(parsed.null_count(), 2);
+///
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
+/// jiff support, behind the `jiff` feature
+///
+/// ```
+/// use chrono::prelude::*;
+/// use jiff::tz::TimeZone;
+/// use qsv_dateparser::{datetime::Parse, jiff::parse_to_zoned};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let parse = Parse::new(&Utc, NaiveTime::MIN);
+///     let zoned = parse_to_zoned(&parse, &TimeZone::get("Europe/Paris")?, "2021-05-14 18:51")?;
+///     assert_eq!(zoned.to_string(), "2021-05-14T18:51:00+02:00[Europe/Paris]");
+///
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "jiff")]
+pub mod jiff;
+
+/// Polars string series to datetime series conversion, behind the `polars` feature
+///
+/// ```
+/// use chrono::prelude::*;
+/// use polars_core::prelude::{NamedFrom, Series, TimeUnit};
+/// use qsv_dateparser::{datetime::Parse, polars::to_datetime};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let parse = Parse::new_with_preference(&Utc, NaiveTime::MIN, true);
+///     let column = Series::new("when".into(), ["2021-05-14 18:51 PDT", "31/07/2021", "n/a"]);
+///
+///     let parsed = to_datetime(&parse, &column, TimeUnit::Milliseconds, None, false)?;
+///     assert_eq!(parsed.null_count(), 1);
+///
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "polars")]
+pub mod polars;
+
+/// Serde support, behind the `serde` feature
+///
+/// ```
+/// use chrono::prelude::*;
+/// use serde::Deserialize;
+/// use std::error::Error;
+///
+/// #[derive(Deserialize)]
+/// struct Record {
+///     #[serde(with = "qsv_dateparser::serde::utc")]
+///     created: DateTime<Utc>,
+///     #[serde(with = "qsv_dateparser::serde::utc_dmy::option")]
+///     due: Option<DateTime<Utc>>,
+/// }
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let record: Record =
+///         serde_json::from_str(r#"{"created": "2021-05-14 18:51 PDT", "due": "31/07/2021 10:00"}"#)?;
+///     assert_eq!(record.created, Utc.with_ymd_and_hms(2021, 5, 15, 1, 51, 0).unwrap());
+///     assert_eq!(record.due, Utc.with_ymd_and_hms(2021, 7, 31, 10, 0, 0).single());
+///
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub mod serde;
+
+/// `time` crate support, behind the `time` feature
+///
+/// ```
+/// use chrono::prelude::*;
+/// use qsv_dateparser::{datetime::Parse, time::parse_to_offset_datetime};
+/// use std::error::Error;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+///     let parse = Parse::new(&Utc, NaiveTime::MIN);
+///     let parsed = parse_to_offset_datetime(&parse, "2021-05-14 18:51 PDT")?;
+///     assert_eq!(parsed.unix_timestamp(), 1621043460);
+///     assert_eq!(parsed.offset().whole_hours(), -7);
+///
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "time")]
+pub mod time;
+
+mod format;
+mod lexer;
+
 use crate::datetime::Parse;
 use anyhow::{Error, Result};
 use chrono::prelude::*;
@@ -219,7 +401,6 @@ use std::sync::OnceLock;
 ///     Err(err) => println!("ERROR from parsing datetime string: {}", err)
 /// }
 /// ```
-
 pub struct DateTimeUtc(pub DateTime<Utc>);
 
 impl std::str::FromStr for DateTimeUtc {
@@ -241,6 +422,66 @@ pub fn parse(input: &str) -> Result<DateTime<Utc>> {
     Parse::new(&Local, Utc::now().time()).parse(input)
 }
 
+/// Similar to [`parse()`], this function takes the raw bytes of a field, such as the ones a CSV
+/// reader hands out, which must be valid UTF-8. See
+/// [`datetime::Parse::parse_bytes`].
+#[inline]
+pub fn parse_bytes(input: &[u8]) -> Result<DateTime<Utc>> {
+    Parse::new(&Local, Utc::now().time()).parse_bytes(input)
+}
+
+/// Similar to [`parse()`], but the result keeps the offset the input was written with. An input
+/// without one is assumed to be a [`chrono::Local`] datetime and gets its offset. See
+/// [`datetime::Parse::parse_fixed_offset`].
+#[inline]
+pub fn parse_fixed_offset(input: &str) -> Result<DateTime<FixedOffset>> {
+    Parse::new(&Local, Utc::now().time()).parse_fixed_offset(input)
+}
+
+/// Similar to [`parse_fixed_offset()`], but an input without an offset gets the offset of the
+/// custom timezone `tz` instead.
+#[inline]
+pub fn parse_fixed_offset_with_timezone<Tz2: TimeZone>(
+    input: &str,
+    tz: &Tz2,
+) -> Result<DateTime<FixedOffset>> {
+    Parse::new(tz, Utc::now().time()).parse_fixed_offset(input)
+}
+
+/// This function returns the strftime pattern the input is written in, such as
+/// `%Y-%m-%d %H:%M:%S`, or `None` when it doesn't parse or strftime can't express it. See
+/// [`datetime::Parse::detect_format`].
+#[inline]
+pub fn detect_format(input: &str) -> Option<String> {
+    Parse::new(&Local, Utc::now().time()).detect_format(input)
+}
+
+/// Similar to [`parse()`], but an input without an offset is returned as written, as a
+/// [`datetime::Reading::Naive`] date and time or a [`datetime::Reading::NaiveDate`], so the caller
+/// can decide how to localize it. An input with an offset is a [`datetime::Reading::Offset`]. See
+/// [`datetime::Parse::parse_naive`].
+#[inline]
+pub fn parse_naive(input: &str) -> Result<datetime::Reading> {
+    Parse::new(&Local, Utc::now().time()).parse_naive(input)
+}
+
+/// Similar to [`parse()`], this function parses the input into the half-open interval
+/// `[start, end)` it covers. Partial dates such as `2021-05` cover their whole period, and explicit
+/// ranges such as `2021-05-01 to 2021-05-31` or `2021-05-01/2021-05-31` are also accepted. See
+/// [`datetime::Parse::parse_interval`].
+#[inline]
+pub fn parse_interval(input: &str) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    Parse::new(&Local, Utc::now().time()).parse_interval(input)
+}
+
+/// This function parses an elapsed time, such as an ISO 8601 duration (`PT1H30M`), a clock time
+/// (`01:30:00`) or a human form (`1h 30m`, `90 min`), into a [`chrono::Duration`]. See
+/// [`duration::parse()`] for the accepted forms.
+#[inline]
+pub fn parse_duration(input: &str) -> Result<chrono::Duration> {
+    duration::parse(input)
+}
+
 /// Similar to [`parse()`], this function takes a datetime string and a boolean `dmy_preference`.
 /// When `dmy_preference` is `true`, it will parse strings using the DMY format. Otherwise, it
 /// parses them using an MDY format.
@@ -250,6 +491,13 @@ pub fn parse_with_preference(input: &str, dmy_preference: bool) -> Result<DateTi
     Parse::new_with_preference(&Utc, *midnight, dmy_preference).parse(input)
 }
 
+/// The byte-slice equivalent of [`parse_with_preference()`].
+#[inline]
+pub fn parse_bytes_with_preference(input: &[u8], dmy_preference: bool) -> Result<DateTime<Utc>> {
+    let midnight = MIDNIGHT.get_or_init(|| NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+    Parse::new_with_preference(&Utc, *midnight, dmy_preference).parse_bytes(input)
+}
+
 /// Similar to [`parse()`], this function takes a datetime string and a custom [`chrono::TimeZone`],
 /// and tries to parse the datetime string. When timezone is not given in the string, this function
 /// will assume and parse the datetime by the custom timezone provided in this function's arguments.
@@ -258,6 +506,12 @@ pub fn parse_with_timezone<Tz2: TimeZone>(input: &str, tz: &Tz2) -> Result<DateT
     Parse::new(tz, Utc::now().time()).parse(input)
 }
 
+/// The byte-slice equivalent of [`parse_with_timezone()`].
+#[inline]
+pub fn parse_bytes_with_timezone<Tz2: TimeZone>(input: &[u8], tz: &Tz2) -> Result<DateTime<Utc>> {
+    Parse::new(tz, Utc::now().time()).parse_bytes(input)
+}
+
 /// Similar to [`parse()`], this function takes a datetime string and a boolean `dmy_preference`
 /// and a timezone. When timezone is not given in the input string, this function will
 /// assume and parse the datetime by the custom timezone provided in this function's arguments.
@@ -273,6 +527,17 @@ pub fn parse_with_preference_and_timezone<Tz2: TimeZone>(
     Parse::new_with_preference(tz, *midnight, dmy_preference).parse(input)
 }
 
+/// The byte-slice equivalent of [`parse_with_preference_and_timezone()`].
+#[inline]
+pub fn parse_bytes_with_preference_and_timezone<Tz2: TimeZone>(
+    input: &[u8],
+    dmy_preference: bool,
+    tz: &Tz2,
+) -> Result<DateTime<Utc>> {
+    let midnight = MIDNIGHT.get_or_init(|| NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+    Parse::new_with_preference(tz, *midnight, dmy_preference).parse_bytes(input)
+}
+
 /// Similar to [`parse()`] and [`parse_with_timezone()`], this function takes a datetime string, a
 /// custom [`chrono::TimeZone`] and a default naive time. In addition to assuming timezone when
 /// it's not given in datetime string, this function also use provided default naive time in parsed
@@ -578,6 +843,18 @@ mod tests {
                 Utc.ymd(2014, 3, 31).and_time(Utc::now().time()).unwrap(),
                 Trunc::Seconds,
             ),
+            (
+                "asctime",
+                "Wed Jun  2 06:31:39 2021",
+                Utc.ymd(2021, 6, 2).and_hms(6, 31, 39),
+                Trunc::None,
+            ),
+            (
+                "unix_date",
+                "Wed Jun  2 06:31:39 PDT 2021",
+                Utc.ymd(2021, 6, 2).and_hms(13, 31, 39),
+                Trunc::None,
+            ),
         ];
 
         for &(test, input, want, trunc) in test_cases.iter() {
@@ -772,4 +1049,77 @@ mod tests {
             Utc.ymd(2021, 7, 31)
         );
     }
+
+    #[test]
+    fn parse_bytes() {
+        assert_eq!(
+            super::parse_bytes_with_timezone(b"2021-04-30 21:14:10", &Utc).unwrap(),
+            Utc.ymd(2021, 4, 30).and_hms(21, 14, 10)
+        );
+        assert_eq!(
+            super::parse_bytes_with_preference(b"31/07/2021 10:00", true).unwrap(),
+            Utc.ymd(2021, 7, 31).and_hms(10, 0, 0)
+        );
+        assert_eq!(
+            super::parse_bytes_with_preference_and_timezone(b"01/07/2021 10:00", true, &Utc)
+                .unwrap(),
+            Utc.ymd(2021, 7, 1).and_hms(10, 0, 0)
+        );
+        assert!(super::parse_bytes(b"\xff").is_err());
+    }
+
+    #[test]
+    fn parse_fixed_offset() {
+        let tz = chrono_tz::America::New_York;
+        let test_cases = [
+            ("2017-11-25 13:31:15 PST", "2017-11-25T13:31:15-08:00"),
+            ("2014-04-26 13:13:44 +09:00", "2014-04-26T13:13:44+09:00"),
+            ("May 02, 2021 15:51:31 UTC", "2021-05-02T15:51:31+00:00"),
+            ("May 26, 2021, 12:49 AM PDT", "2021-05-26T00:49:00-07:00"),
+            (
+                "2021-05-01T01:17:02.604456+02:00",
+                "2021-05-01T01:17:02.604456+02:00",
+            ),
+            (
+                "Wed, 02 Jun 2021 06:31:39 -0400",
+                "2021-06-02T06:31:39-04:00",
+            ),
+            ("10/Oct/2000:13:55:36 -0700", "2000-10-10T13:55:36-07:00"),
+            ("2021-04-30 21:14:10", "2021-04-30T21:14:10-04:00"),
+            ("2021-01-30 21:14:10", "2021-01-30T21:14:10-05:00"),
+            ("1620021848", "2021-05-03T06:04:08+00:00"),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                super::parse_fixed_offset_with_timezone(input, &tz)
+                    .unwrap()
+                    .to_rfc3339(),
+                want,
+                "parse_fixed_offset/{}",
+                input
+            );
+        }
+        assert!(super::parse_fixed_offset("not-a-date").is_err());
+    }
+
+    #[test]
+    fn parse_naive() {
+        assert_eq!(
+            super::parse_naive("2021-04-30 21:14:10").unwrap(),
+            datetime::Reading::Naive(NaiveDate::from_ymd(2021, 4, 30).and_hms(21, 14, 10))
+        );
+        assert_eq!(
+            super::parse_naive("2021-04-30").unwrap(),
+            datetime::Reading::NaiveDate(NaiveDate::from_ymd(2021, 4, 30))
+        );
+        assert_eq!(
+            super::parse_naive("2021-04-30 21:14:10 +0800").unwrap(),
+            datetime::Reading::Offset(
+                FixedOffset::east(8 * 3600)
+                    .ymd(2021, 4, 30)
+                    .and_hms(21, 14, 10)
+            )
+        );
+    }
 }
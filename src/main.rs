@@ -0,0 +1,424 @@
+//! Command-line front end to the library: parses each value given as an argument, or each line of
+//! stdin when there is none, and prints it in a uniform format. With `--csv`, the selected columns
+//! of a CSV file are rewritten in that format instead.
+
+use anyhow::{anyhow, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::prelude::*;
+use clap::Parser;
+use qsv_dateparser::datetime::Parse;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "qsv-dateparser", version, about)]
+struct Args {
+    /// Values to parse. Lines of stdin are read when there are none.
+    #[arg(conflicts_with = "csv")]
+    values: Vec<String>,
+
+    /// CSV file whose selected columns are rewritten, or - for stdin. Other columns are left
+    /// untouched, and so are the values that don't parse.
+    #[arg(long, requires = "select", conflicts_with = "explain")]
+    csv: Option<PathBuf>,
+
+    /// Columns of the CSV file to rewrite, by name or by 1-based index, separated by commas.
+    #[arg(long, value_delimiter = ',', requires = "csv")]
+    select: Vec<String>,
+
+    /// Field delimiter of the CSV file.
+    #[arg(long, default_value_t = ',')]
+    delimiter: char,
+
+    /// Read ambiguous dates such as 01/07/2021 day first.
+    #[arg(long)]
+    dmy: bool,
+
+    /// Timezone of the values without an offset: "local", an IANA name such as
+    /// America/New_York, or an offset such as +09:00.
+    #[arg(long, default_value = "local")]
+    tz: String,
+
+    /// Time of day of the values without one, as hh:mm or hh:mm:ss in the timezone of --tz.
+    #[arg(long, default_value = "00:00:00")]
+    default_time: String,
+
+    /// Stop at the first value that doesn't parse, instead of printing an empty line for it, or
+    /// leaving it as is in a CSV file.
+    #[arg(long)]
+    strict: bool,
+
+    /// Output format: "rfc3339", "epoch" (seconds), or a strftime format. Always in UTC.
+    #[arg(long, default_value = "rfc3339")]
+    format: String,
+
+    /// Also print the family of formats that matched, after a tab.
+    #[arg(long)]
+    explain: bool,
+}
+
+enum Output<'a> {
+    Rfc3339,
+    Epoch,
+    Strftime(Vec<Item<'a>>),
+}
+
+impl<'a> Output<'a> {
+    fn new(format: &'a str) -> Result<Self> {
+        Ok(match format {
+            "rfc3339" => Output::Rfc3339,
+            "epoch" => Output::Epoch,
+            _ => {
+                let items: Vec<Item> = StrftimeItems::new(format).collect();
+                if items.iter().any(|item| matches!(item, Item::Error)) {
+                    return Err(anyhow!("{} is not a valid strftime format.", format));
+                }
+                Output::Strftime(items)
+            }
+        })
+    }
+
+    fn write(&self, out: &mut impl Write, datetime: &DateTime<Utc>) -> io::Result<()> {
+        match self {
+            Output::Rfc3339 => out.write_all(
+                datetime
+                    .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                    .as_bytes(),
+            ),
+            Output::Epoch => write!(out, "{}", datetime.timestamp()),
+            Output::Strftime(items) => write!(out, "{}", datetime.format_with_items(items.iter())),
+        }
+    }
+}
+
+// `Parse` takes the default time as a UTC time of day, and converts it to the timezone, so the
+// time of day in the timezone is converted to UTC first, at today's offset.
+fn default_time<Tz2: TimeZone>(tz: &Tz2, input: &str) -> Result<NaiveTime> {
+    let local = NaiveTime::parse_from_str(input, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(input, "%H:%M"))
+        .map_err(|_| anyhow!("{} is not a valid time of day.", input))?;
+    let today = Utc::now().date_naive().and_time(local);
+    let offset = tz.offset_from_utc_datetime(&today).fix().local_minus_utc();
+    Ok((today - chrono::Duration::seconds(i64::from(offset))).time())
+}
+
+fn new_parse<'z, Tz2: TimeZone>(tz: &'z Tz2, args: &Args) -> Result<Parse<'z, Tz2>> {
+    Ok(Parse::new_with_preference(
+        tz,
+        default_time(tz, &args.default_time)?,
+        args.dmy,
+    ))
+}
+
+// Parses and prints each value in turn, reporting the ones that don't parse. Returns whether they
+// all parsed.
+fn run<Tz2, I>(
+    tz: &Tz2,
+    args: &Args,
+    values: I,
+    out: &mut impl Write,
+    report: &mut impl Write,
+) -> Result<bool>
+where
+    Tz2: TimeZone,
+    I: IntoIterator<Item = io::Result<String>>,
+{
+    let parse = new_parse(tz, args)?;
+    let output = Output::new(&args.format)?;
+
+    let mut parsed_all = true;
+    for value in values {
+        let value = value?;
+        match parse.explain(value.trim()) {
+            Ok((datetime, family)) => {
+                output.write(out, &datetime)?;
+                if args.explain {
+                    write!(out, "\t{}", family)?;
+                }
+            }
+            Err(err) if args.strict => return Err(err),
+            Err(err) => {
+                writeln!(report, "{}", err)?;
+                parsed_all = false;
+            }
+        }
+        writeln!(out)?;
+    }
+    out.flush()?;
+    Ok(parsed_all)
+}
+
+// Finds the index of each selected column, by name or else by 1-based index.
+fn select(headers: &csv::StringRecord, selectors: &[String]) -> Result<Vec<usize>> {
+    selectors
+        .iter()
+        .map(|selector| {
+            headers
+                .iter()
+                .position(|header| header == selector)
+                .or_else(|| match selector.parse::<usize>() {
+                    Ok(index) if (1..=headers.len()).contains(&index) => Some(index - 1),
+                    _ => None,
+                })
+                .ok_or_else(|| anyhow!("{} is not a column.", selector))
+        })
+        .collect()
+}
+
+// Copies the CSV input to `out`, rewriting the non-empty values of the selected columns, and
+// reports the values that don't parse with their line and column. Returns whether they all parsed.
+fn normalize<Tz2>(
+    tz: &Tz2,
+    args: &Args,
+    input: impl Read,
+    out: impl Write,
+    report: &mut impl Write,
+) -> Result<bool>
+where
+    Tz2: TimeZone,
+{
+    let parse = new_parse(tz, args)?;
+    let output = Output::new(&args.format)?;
+    let delimiter = u8::try_from(args.delimiter)
+        .ok()
+        .filter(u8::is_ascii)
+        .ok_or_else(|| anyhow!("{} is not an ASCII delimiter.", args.delimiter))?;
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .from_reader(input);
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(out);
+    let headers = reader.headers()?.clone();
+    let columns = select(&headers, &args.select)?;
+    writer.write_record(&headers)?;
+
+    let (mut rows, mut failed) = (0, 0);
+    let mut record = csv::StringRecord::new();
+    let mut cell = Vec::new();
+    let mut rewritten = csv::ByteRecord::new();
+    while reader.read_record(&mut record)? {
+        rows += 1;
+        let line = record.position().map_or(0, |position| position.line());
+        let mut row_failed = false;
+        rewritten.clear();
+        for (i, value) in record.iter().enumerate() {
+            if !columns.contains(&i) || value.trim().is_empty() {
+                rewritten.push_field(value.as_bytes());
+                continue;
+            }
+            match parse.parse(value.trim()) {
+                Ok(datetime) => {
+                    cell.clear();
+                    output.write(&mut cell, &datetime)?;
+                    rewritten.push_field(&cell);
+                }
+                Err(err) => {
+                    let err = anyhow!("line {}, column {}: {}", line, &headers[i], err);
+                    if args.strict {
+                        writer.flush()?;
+                        return Err(err);
+                    }
+                    writeln!(report, "{}", err)?;
+                    row_failed = true;
+                    rewritten.push_field(value.as_bytes());
+                }
+            }
+        }
+        failed += usize::from(row_failed);
+        writer.write_byte_record(&rewritten)?;
+    }
+    writer.flush()?;
+    if failed > 0 {
+        writeln!(report, "{} of {} rows failed to parse.", failed, rows)?;
+    }
+    Ok(failed == 0)
+}
+
+// Runs the mode the arguments ask for, reading values or CSV from the arguments, a file or stdin.
+fn dispatch<Tz2: TimeZone>(tz: &Tz2, args: &Args) -> Result<bool> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut report = io::stderr().lock();
+    match &args.csv {
+        Some(path) if path.as_os_str() == "-" => {
+            normalize(tz, args, io::stdin().lock(), &mut out, &mut report)
+        }
+        Some(path) => {
+            let file = File::open(path).map_err(|err| anyhow!("{}: {}", path.display(), err))?;
+            normalize(tz, args, file, &mut out, &mut report)
+        }
+        None if args.values.is_empty() => {
+            run(tz, args, io::stdin().lock().lines(), &mut out, &mut report)
+        }
+        None => {
+            let values = args.values.iter().cloned().map(Ok);
+            run(tz, args, values, &mut out, &mut report)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let ran = match args.tz.as_str() {
+        "local" => dispatch(&Local, &args),
+        tz if tz.starts_with(['+', '-']) => {
+            qsv_dateparser::timezone::parse(tz).and_then(|tz| dispatch(&tz, &args))
+        }
+        tz => tz
+            .parse::<chrono_tz::Tz>()
+            .map_err(|_| anyhow!("{} is not a known timezone.", tz))
+            .and_then(|tz| dispatch(&tz, &args)),
+    };
+    match ran {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(2),
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_with(flags: &[&str], values: &[&str]) -> (Result<bool>, String) {
+        run_in(&Utc, flags, values)
+    }
+
+    fn run_in<Tz2: TimeZone>(tz: &Tz2, flags: &[&str], values: &[&str]) -> (Result<bool>, String) {
+        let args = Args::parse_from(["qsv-dateparser"].iter().chain(flags));
+        let mut out = Vec::new();
+        let values = values.iter().map(|value| Ok(value.to_string()));
+        let ran = run(tz, &args, values, &mut out, &mut io::sink());
+        (ran, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn formats() {
+        let values = ["2021-05-14 18:51 PDT", "1620021848", "01/07/2021"];
+        let (ran, out) = run_with(&[], &values);
+        assert!(ran.unwrap());
+        assert_eq!(
+            out,
+            "2021-05-15T01:51:00Z\n2021-05-03T06:04:08Z\n2021-01-07T00:00:00Z\n"
+        );
+
+        let (_, out) = run_with(&["--dmy", "--default-time", "10:30"], &values[2..]);
+        assert_eq!(out, "2021-07-01T10:30:00Z\n");
+
+        let (_, out) = run_with(&["--format", "epoch"], &values[..2]);
+        assert_eq!(out, "1621043460\n1620021848\n");
+
+        let (_, out) = run_with(&["--format", "%Y/%m/%d %H:%M"], &values[..1]);
+        assert_eq!(out, "2021/05/15 01:51\n");
+
+        let (ran, _) = run_with(&["--format", "%Q"], &values);
+        assert!(ran.is_err());
+    }
+
+    #[test]
+    fn default_time() {
+        let values = ["2021-02-21", "2021-07-01", "2021-02-21 18:51"];
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let (_, out) = run_in(&tokyo, &["--default-time", "12:00"], &values);
+        assert_eq!(
+            out,
+            "2021-02-21T03:00:00Z\n2021-07-01T03:00:00Z\n2021-02-21T09:51:00Z\n"
+        );
+
+        let (_, out) = run_in(&chrono_tz::Asia::Tokyo, &[], &values[..2]);
+        assert_eq!(out, "2021-02-20T15:00:00Z\n2021-06-30T15:00:00Z\n");
+        let (_, out) = run_in(&chrono_tz::America::New_York, &[], &values[..2]);
+        assert_eq!(out, "2021-02-21T05:00:00Z\n2021-07-01T04:00:00Z\n");
+    }
+
+    #[test]
+    fn explain_and_strict() {
+        let values = [
+            "2021-04-30 21:14:10",
+            "not-a-date",
+            "May 8, 2009 5:57:51 PM",
+        ];
+        let (ran, out) = run_with(&["--explain"], &values);
+        assert!(!ran.unwrap());
+        assert_eq!(
+            out,
+            "2021-04-30T21:14:10Z\tyyyy-mm-dd\n\n2009-05-08T17:57:51Z\tMon dd, yyyy\n"
+        );
+
+        let (ran, out) = run_with(&["--strict"], &values);
+        assert_eq!(
+            ran.unwrap_err().to_string(),
+            "not-a-date did not match any formats."
+        );
+        assert_eq!(out, "2021-04-30T21:14:10Z\n");
+    }
+
+    fn normalize_with(flags: &[&str], input: &str) -> (Result<bool>, String, String) {
+        let args = Args::parse_from(["qsv-dateparser", "--csv", "-"].iter().chain(flags));
+        let (mut out, mut report) = (Vec::new(), Vec::new());
+        let ran = normalize(&Utc, &args, input.as_bytes(), &mut out, &mut report);
+        (
+            ran,
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(report).unwrap(),
+        )
+    }
+
+    #[test]
+    fn csv() {
+        let input = "\
+id,created,note,due
+1,2021-05-14 18:51 PDT,\"May 8, 2009\",01/07/2021
+2,,keep,not-a-date
+3,1620021848,x,2021-02-21
+";
+        let (ran, out, report) = normalize_with(&["--select", "created,4"], input);
+        assert!(!ran.unwrap());
+        assert_eq!(
+            out,
+            "\
+id,created,note,due
+1,2021-05-15T01:51:00Z,\"May 8, 2009\",2021-01-07T00:00:00Z
+2,,keep,not-a-date
+3,2021-05-03T06:04:08Z,x,2021-02-21T00:00:00Z
+"
+        );
+        assert_eq!(
+            report,
+            "line 3, column due: not-a-date did not match any formats.\n1 of 3 rows failed to parse.\n"
+        );
+
+        let (ran, out, _) = normalize_with(
+            &[
+                "--select",
+                "due",
+                "--delimiter",
+                ";",
+                "--format",
+                "%F",
+                "--dmy",
+            ],
+            "id;due\n1;01/07/2021\n",
+        );
+        assert!(ran.unwrap());
+        assert_eq!(out, "id;due\n1;2021-07-01\n");
+
+        let (ran, out, _) = normalize_with(&["--select", "due", "--strict"], input);
+        assert_eq!(
+            ran.unwrap_err().to_string(),
+            "line 3, column due: not-a-date did not match any formats."
+        );
+        assert!(out.starts_with("id,created,note,due\n1,2021-05-14 18:51 PDT,"));
+
+        let (ran, _, _) = normalize_with(&["--select", "missing"], input);
+        assert_eq!(ran.unwrap_err().to_string(), "missing is not a column.");
+        let (ran, _, _) = normalize_with(&["--select", "5"], input);
+        assert!(ran.is_err());
+    }
+}
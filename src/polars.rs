@@ -0,0 +1,108 @@
+use crate::datetime::{Batch, Parse};
+use anyhow::{anyhow, Result};
+use chrono::prelude::*;
+use polars_core::prelude::{Int64Chunked, IntoSeries, Series, TimeUnit};
+
+/// Parses each value of a string `Series` into a `Datetime` series of the given unit, with
+/// `time_zone` as its metadata, keeping the name of the input. Like Polars' own `str.to_datetime`
+/// but without a format: each value goes through the same formats as [`Parse::parse`], so the
+/// `prefer_dmy` preference and the timezone of `parse` apply. Whatever the metadata, the values
+/// count from the unix epoch in UTC.
+///
+/// Null values stay null. A value that doesn't parse, or that is out of range for the unit, is
+/// null too, unless `strict` is set, in which case the first one is returned as an error.
+pub fn to_datetime<Tz2>(
+    parse: &Parse<'_, Tz2>,
+    series: &Series,
+    unit: TimeUnit,
+    time_zone: Option<&str>,
+    strict: bool,
+) -> Result<Series>
+where
+    Tz2: TimeZone,
+{
+    let strings = series.str()?;
+    let time_zone = polars_core::prelude::TimeZone::opt_try_new(time_zone)?;
+    let to_value = match unit {
+        TimeUnit::Milliseconds => |datetime: DateTime<Utc>| Some(datetime.timestamp_millis()),
+        TimeUnit::Microseconds => |datetime: DateTime<Utc>| Some(datetime.timestamp_micros()),
+        TimeUnit::Nanoseconds => |datetime: DateTime<Utc>| datetime.timestamp_nanos_opt(),
+    };
+
+    let mut batch = Batch::new(parse);
+    let values = strings
+        .iter()
+        .map(|input| match input {
+            Some(input) => batch.parse_value(input, strict, to_value),
+            None => Ok(None),
+        })
+        .collect::<Result<Int64Chunked>>()
+        .map_err(|err| anyhow!("{}: {}", series.name(), err))?;
+    Ok(values
+        .with_name(series.name().clone())
+        .into_datetime(unit, time_zone)
+        .into_series())
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use super::*;
+    use polars_core::prelude::{AnyValue, DataType, NamedFrom};
+
+    #[test]
+    fn to_datetime() {
+        let parse = Parse::new_with_preference(&Utc, NaiveTime::MIN, true);
+        let series = Series::new(
+            "when".into(),
+            [
+                Some("2021-04-30 21:14:10"),
+                None,
+                Some("not-a-date"),
+                Some("01/07/2021"),
+                Some("May 8, 2009 5:57:51 PM"),
+            ],
+        );
+
+        let parsed =
+            super::to_datetime(&parse, &series, TimeUnit::Milliseconds, Some("UTC"), false)
+                .unwrap();
+        assert_eq!(parsed.name().as_str(), "when");
+        assert_eq!(
+            parsed.dtype(),
+            &DataType::Datetime(
+                TimeUnit::Milliseconds,
+                polars_core::prelude::TimeZone::opt_try_new(Some("UTC")).unwrap()
+            )
+        );
+        assert_eq!(parsed.null_count(), 2);
+
+        let millis = |datetime: DateTime<Utc>| datetime.timestamp_millis();
+        let values: Vec<Option<i64>> = parsed
+            .to_physical_repr()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            values,
+            [
+                Some(millis(Utc.ymd(2021, 4, 30).and_hms(21, 14, 10))),
+                None,
+                None,
+                // day first, as preferred
+                Some(millis(Utc.ymd(2021, 7, 1).and_hms(0, 0, 0))),
+                Some(millis(Utc.ymd(2009, 5, 8).and_hms(17, 57, 51))),
+            ]
+        );
+        assert!(matches!(parsed.get(1), Ok(AnyValue::Null)));
+
+        let err = super::to_datetime(&parse, &series, TimeUnit::Milliseconds, None, true)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "when: not-a-date did not match any formats.");
+
+        let numbers = Series::new("n".into(), [1_i64, 2]);
+        assert!(super::to_datetime(&parse, &numbers, TimeUnit::Milliseconds, None, false).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::datetime::Parse;
+use crate::DateTimeUtc;
+use chrono::prelude::*;
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+use std::fmt;
+
+struct DateTimeVisitor {
+    prefer_dmy: bool,
+}
+
+impl Visitor<'_> for DateTimeVisitor {
+    type Value = DateTime<Utc>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a date and time string in one of the accepted formats")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Parse::new_with_preference(&Utc, NaiveTime::MIN, self.prefer_dmy)
+            .parse(value)
+            .map_err(E::custom)
+    }
+}
+
+struct OptionVisitor {
+    prefer_dmy: bool,
+}
+
+impl<'de> Visitor<'de> for OptionVisitor {
+    type Value = Option<DateTime<Utc>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a date and time string in one of the accepted formats, or none")
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_str(DateTimeVisitor {
+                prefer_dmy: self.prefer_dmy,
+            })
+            .map(Some)
+    }
+}
+
+#[inline]
+fn serialize<S>(datetime: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
+/// Serialize and deserialize a `DateTime<Utc>` field with
+/// `#[serde(with = "qsv_dateparser::serde::utc")]`. It is deserialized from a string in any of
+/// the accepted formats, reading one without an offset as UTC and one without a time of day as
+/// midnight, and serialized to RFC 3339.
+pub mod utc {
+    use super::DateTimeVisitor;
+    use chrono::prelude::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(datetime: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize(datetime, serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(DateTimeVisitor { prefer_dmy: false })
+    }
+
+    /// Like [`crate::serde::utc`], for an `Option<DateTime<Utc>>` field.
+    pub mod option {
+        use crate::serde::OptionVisitor;
+        use chrono::prelude::*;
+        use serde::{Deserializer, Serializer};
+
+        pub fn serialize<S>(
+            datetime: &Option<DateTime<Utc>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match datetime {
+                Some(datetime) => serializer.serialize_some(&crate::DateTimeUtc(*datetime)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_option(OptionVisitor { prefer_dmy: false })
+        }
+    }
+}
+
+/// Like [`crate::serde::utc`], but ambiguous dates such as `01/07/2021` are read day first, like
+/// [`crate::parse_with_preference()`].
+pub mod utc_dmy {
+    use super::DateTimeVisitor;
+    use chrono::prelude::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(datetime: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize(datetime, serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(DateTimeVisitor { prefer_dmy: true })
+    }
+
+    /// Like [`crate::serde::utc_dmy`], for an `Option<DateTime<Utc>>` field.
+    pub mod option {
+        use crate::serde::OptionVisitor;
+        use chrono::prelude::*;
+        use serde::{Deserializer, Serializer};
+
+        pub fn serialize<S>(
+            datetime: &Option<DateTime<Utc>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            crate::serde::utc::option::serialize(datetime, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_option(OptionVisitor { prefer_dmy: true })
+        }
+    }
+}
+
+impl serde::Serialize for DateTimeUtc {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for DateTimeUtc {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        utc::deserialize(deserializer).map(DateTimeUtc)
+    }
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        #[serde(with = "crate::serde::utc")]
+        created: DateTime<Utc>,
+        #[serde(with = "crate::serde::utc::option", default)]
+        updated: Option<DateTime<Utc>>,
+        #[serde(with = "crate::serde::utc_dmy")]
+        due: DateTime<Utc>,
+        #[serde(with = "crate::serde::utc_dmy::option")]
+        closed: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn with() {
+        let record: Record = serde_json::from_str(
+            r#"{
+                "created": "2021-05-14 18:51 PDT",
+                "updated": null,
+                "due": "01/07/2021 10:00",
+                "closed": "May 8, 2009 05:57:51 PM UTC"
+            }"#,
+        )
+        .unwrap();
+        let want = Record {
+            created: Utc.ymd(2021, 5, 15).and_hms(1, 51, 0),
+            updated: None,
+            due: Utc.ymd(2021, 7, 1).and_hms(10, 0, 0),
+            closed: Some(Utc.ymd(2009, 5, 8).and_hms(17, 57, 51)),
+        };
+        assert_eq!(record, want);
+
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"created":"2021-05-15T01:51:00Z","updated":null,"due":"2021-07-01T10:00:00Z","closed":"2009-05-08T17:57:51Z"}"#
+        );
+
+        let err = serde_json::from_str::<Record>(
+            r#"{"created": "not-a-date", "due": "01/07/2021 10:00", "closed": null}"#,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("not-a-date did not match any formats."));
+    }
+
+    #[test]
+    fn date_only() {
+        let record: Record = serde_json::from_str(
+            r#"{"created": "2021-05-14", "due": "01/07/2021", "closed": "2021-05-14 18:51"}"#,
+        )
+        .unwrap();
+        assert_eq!(record.created, Utc.ymd(2021, 5, 14).and_hms(0, 0, 0));
+        assert_eq!(record.due, Utc.ymd(2021, 7, 1).and_hms(0, 0, 0));
+        assert_eq!(record.closed, Some(Utc.ymd(2021, 5, 14).and_hms(18, 51, 0)));
+    }
+
+    #[test]
+    fn date_time_utc() {
+        let parsed: DateTimeUtc =
+            serde_json::from_str(r#""Wed, 02 Jun 2021 06:31:39 GMT""#).unwrap();
+        assert_eq!(parsed.0, Utc.ymd(2021, 6, 2).and_hms(6, 31, 39));
+        assert_eq!(
+            serde_json::to_string(&DateTimeUtc(
+                Utc.ymd(2021, 6, 2).and_hms_milli(6, 31, 39, 250)
+            ))
+            .unwrap(),
+            r#""2021-06-02T06:31:39.250Z""#
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use crate::datetime::{Parse, Reading};
+use anyhow::{anyhow, Result};
+use chrono::prelude::*;
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+/// Parses the input like [`Parse::parse_fixed_offset`], with the same formats, `prefer_dmy`
+/// preference and timezone, into a `time::OffsetDateTime` that keeps the offset the input was
+/// written with, such as the `-07:00` of `2021-05-14 18:51 PDT`.
+///
+/// `time` has no leap seconds, so a leap second is read as the last nanosecond of the second before
+/// it, as with [`crate::datetime::LeapSecond::Clamp`].
+pub fn parse_to_offset_datetime<Tz2>(parse: &Parse<'_, Tz2>, input: &str) -> Result<OffsetDateTime>
+where
+    Tz2: TimeZone,
+{
+    let parsed = parse.parse_fixed_offset(input)?;
+    let offset = UtcOffset::from_whole_seconds(parsed.offset().local_minus_utc());
+    OffsetDateTime::from_unix_timestamp(parsed.timestamp())
+        .and_then(|datetime| datetime.replace_nanosecond(parsed.nanosecond().min(999_999_999)))
+        .ok()
+        .zip(offset.ok())
+        .and_then(|(datetime, offset)| datetime.checked_to_offset(offset))
+        .ok_or_else(|| anyhow!("{} is out of range.", input))
+}
+
+/// Parses the input like [`Parse::parse_naive`] into a `time::PrimitiveDateTime`, the date and time
+/// as written: an input without an offset isn't localized, so a time skipped by a DST transition in
+/// the timezone of `parse` still parses, and an input with an offset keeps its wall-clock time. A
+/// date without a time of day gets the default time of `parse`.
+pub fn parse_to_primitive_datetime<Tz2>(
+    parse: &Parse<'_, Tz2>,
+    input: &str,
+) -> Result<PrimitiveDateTime>
+where
+    Tz2: TimeZone,
+{
+    let local = match parse.parse_naive(input)? {
+        Reading::Offset(datetime) => datetime.naive_local(),
+        Reading::Naive(local) => local,
+        Reading::NaiveDate(date) => date.and_time(parse.default_time()),
+    };
+    let date = Month::try_from(local.month() as u8)
+        .and_then(|month| Date::from_calendar_date(local.year(), month, local.day() as u8));
+    let time = Time::from_hms_nano(
+        local.hour() as u8,
+        local.minute() as u8,
+        local.second() as u8,
+        local.nanosecond().min(999_999_999),
+    );
+    match (date, time) {
+        (Ok(date), Ok(time)) => Ok(PrimitiveDateTime::new(date, time)),
+        _ => Err(anyhow!("{} is out of range.", input)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn parse_to_offset_datetime() {
+        let parse = Parse::new(&Utc, NaiveTime::MIN);
+        let test_cases = [
+            ("2021-05-14 18:51 PDT", datetime!(2021-05-14 18:51 -07:00)),
+            (
+                "2021-05-14T18:51:00+05:30",
+                datetime!(2021-05-14 18:51 +05:30),
+            ),
+            ("1620021848", datetime!(2021-05-03 06:04:08 UTC)),
+            (
+                "2021-04-30 21:14:10.052282",
+                datetime!(2021-04-30 21:14:10.052282 UTC),
+            ),
+            (
+                "2016-12-31 23:59:60",
+                datetime!(2016-12-31 23:59:59.999999999 UTC),
+            ),
+            ("May 25, 2021", datetime!(2021-05-25 00:00 UTC)),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            let parsed = super::parse_to_offset_datetime(&parse, input).unwrap();
+            assert_eq!(parsed, want, "parse_to_offset_datetime/{}", input);
+            assert_eq!(
+                parsed.offset(),
+                want.offset(),
+                "parse_to_offset_datetime/{}",
+                input
+            );
+        }
+        assert!(super::parse_to_offset_datetime(&parse, "not-a-date").is_err());
+    }
+
+    #[test]
+    fn parse_to_primitive_datetime() {
+        let parse = Parse::new(&chrono_tz::America::New_York, NaiveTime::MIN);
+        let test_cases = [
+            ("2021-04-30 21:14:10", datetime!(2021-04-30 21:14:10)),
+            // skipped by the DST transition in New York
+            ("2021-03-14 02:30:00", datetime!(2021-03-14 02:30)),
+            ("2021-05-14 18:51 PDT", datetime!(2021-05-14 18:51)),
+            ("2021-05-14T18:51:00+05:30", datetime!(2021-05-14 18:51)),
+            ("1620021848", datetime!(2021-05-03 06:04:08)),
+            ("May 25, 2021", datetime!(2021-05-25 00:00)),
+        ];
+
+        for &(input, want) in test_cases.iter() {
+            assert_eq!(
+                super::parse_to_primitive_datetime(&parse, input).unwrap(),
+                want,
+                "parse_to_primitive_datetime/{}",
+                input
+            );
+        }
+    }
+}